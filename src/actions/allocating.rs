@@ -2,12 +2,13 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use crate::data::game_state::{Color, GameState, PlayerArea, ResourceSlot, ResourceType, TurnState, HitTarget, RaidTarget};
+use crate::data::game_state::{Action, Color, GameState, PlayerArea, ResourceSlot, ResourceType, TurnState, HitTarget, RaidTarget};
+use crate::data::violations::RuleViolation;
 
-pub (crate) fn allocate_resources(game_state: &GameState, configuration: Vec<(u8, ResourceType)>) -> GameState {
+pub (crate) fn allocate_resources(game_state: &GameState, configuration: Vec<(u8, ResourceType)>) -> Result<GameState, RuleViolation> {
     let (current_player, additional_resources) = match &game_state.turn_state {
         TurnState::AllocateResources { player, resources } => (player, resources),
-        _ => panic!("Cannot allocate resources in {:?}", game_state.turn_state)
+        _ => return Err(RuleViolation::WrongTurnState { turn_state: game_state.turn_state.clone(), action: Action::AllocateResources { configuration: configuration } })
     };
     let current_resource_slots = game_state.players.get(&current_player).unwrap().resource_slots.clone();
 
@@ -30,9 +31,11 @@ pub (crate) fn allocate_resources(game_state: &GameState, configuration: Vec<(u8
         })
         .count();
 
-    if configuration.len() > available_resourceslots {panic!("Cannot allocate {:?} resources into {:?} available ResourceSlots", configuration.len(), available_resourceslots)}
+    if configuration.len() > available_resourceslots {return Err(RuleViolation::TooManyResources { resources: configuration.len() as u8, slots: available_resourceslots as u8 })}
 
-    if configuration.iter().map(|(i,_)|i).tuple_windows().any(|(i1,i2)| i1==i2) {panic!("Configuration includes index duplicates")}
+    if let Some((i, _)) = configuration.iter().map(|(i,_)|i).tuple_combinations().find(|(i1,i2)| i1==i2) {return Err(RuleViolation::DuplicateSlot { slot: *i })}
+
+    if let Some((i, _)) = configuration.iter().find(|(i, _)| matches!(current_resource_slots.get(*i as usize), Some(ResourceSlot::Covered { .. }) | None)) {return Err(RuleViolation::SlotCovered { slot: *i })}
 
     let config_resources = configuration
         .iter()
@@ -71,8 +74,8 @@ pub (crate) fn allocate_resources(game_state: &GameState, configuration: Vec<(u8
             },
         );
 
-    if config_resources.iter().any(|(resource, &config_count)| config_count > *available_resources.get(resource).unwrap()) {
-        panic!("Trying to allocate more resources than available: config: {:?}, available: {:?}", config_resources, available_resources);
+    if let Some((resource, &config_count)) = config_resources.iter().find(|(resource, config_count)| **config_count > *available_resources.get(resource).unwrap()) {
+        return Err(RuleViolation::ResourceNotAvailable { resource: resource.clone(), allocated: config_count, available: *available_resources.get(resource).unwrap() });
     }
 
     let new_resource_slots: Vec<ResourceSlot> = current_resource_slots
@@ -89,7 +92,7 @@ pub (crate) fn allocate_resources(game_state: &GameState, configuration: Vec<(u8
                 (ResourceSlot::Used { keys, ..} | ResourceSlot::Unused { keys } , None) => ResourceSlot::Unused { keys: *keys },
                 (ResourceSlot::Used { keys, ..} | ResourceSlot::Unused { keys }, Some(resource)) => ResourceSlot::Used { keys: *keys, resource: resource },
                 (ResourceSlot::Covered { keys }, None) => ResourceSlot::Covered { keys: *keys },
-                (ResourceSlot::Covered { ..}, Some(_)) => panic!("Cannot allocate Resource in Covered ResourceSlot"),
+                (ResourceSlot::Covered { ..}, Some(_)) => unreachable!("Covered ResourceSlots are rejected above"),
             }
         })
         .collect();
@@ -118,27 +121,28 @@ pub (crate) fn allocate_resources(game_state: &GameState, configuration: Vec<(u8
         })
         .collect();
 
-    GameState {
+    Ok(GameState {
         players: new_players,
         resource_reserve: new_resource_reserve,
         turn_state: game_state.next_turn_states[0].clone(),
         next_turn_states: game_state.next_turn_states[1..].to_vec(),
         .. game_state.clone()
-    }
+    })
 }
 
-pub (crate) fn allocate_dice_results(game_state: &GameState, target_system: u8, target_player: Color, self_hits: u8, hits: u8, building_hits: u8, keys: u8 , own_hits: Vec<HitTarget>, other_hits: Vec<HitTarget>, raid_target: Vec<RaidTarget>) -> GameState{
+// Does not apply hits yet. Destroyed Cities have to go back with change_cities_in_reserve, so their owner covers slots again
+pub (crate) fn allocate_dice_results(game_state: &GameState, target_system: u8, target_player: Color, self_hits: u8, hits: u8, building_hits: u8, keys: u8 , own_hits: Vec<HitTarget>, other_hits: Vec<HitTarget>, raid_target: Vec<RaidTarget>) -> Result<GameState, RuleViolation> {
     let (system_type, building_slots, ships) = match game_state.systems[target_system as usize].clone() {
-        crate::data::system::System::Unused => panic!("Cannot allocate in unused System"),
+        crate::data::system::System::Unused => return Err(RuleViolation::UnusedSystem { system_id: target_system }),
         crate::data::system::System::Used { system_id, system_type, building_slots, ships, controlled_by, connects_to } => (system_type, building_slots, ships),
     };
     
     let hit_sum: u8 = own_hits.iter().map(|h| match h {
-        HitTarget::Damaged => 1,
-        HitTarget::Fresh => 1,
-        HitTarget::Destroyed => 2,
-        HitTarget::Building { .. } => panic!("Cannot hit own buildings, when attacking"),
-    }).sum();
+        HitTarget::Damaged => Ok(1),
+        HitTarget::Fresh => Ok(1),
+        HitTarget::Destroyed => Ok(2),
+        HitTarget::Building { .. } => Err(RuleViolation::CannotHitOwnBuildings),
+    }).sum::<Result<u8, RuleViolation>>()?;
 
     let possible_hits = {
         let s = ships.get(&game_state.current_player).unwrap();
@@ -146,7 +150,7 @@ pub (crate) fn allocate_dice_results(game_state: &GameState, target_system: u8,
     };
 
 
-    if hit_sum < self_hits {panic!("Need to allocate all")}
+    if hit_sum < self_hits {return Err(RuleViolation::HitsNotAllocated { expected: self_hits, allocated: hit_sum })}
 
-    game_state.clone()
+    Ok(game_state.clone())
 }
\ No newline at end of file
@@ -1,17 +1,19 @@
 use crate::board::{place_building, place_ships};
 use crate::data::game_state::{BuildType, GameState};
 use crate::data::system::{BuildingSlot, BuildingType, System};
+use crate::data::violations::RuleViolation;
 
-pub fn build(game_state: &GameState, target_system: u8, build_type: BuildType) -> GameState {
+pub fn build(game_state: &GameState, target_system: u8, build_type: BuildType) -> Result<GameState, RuleViolation> {
     let mut game_state = game_state.clone();
 
     let current_player = game_state.current_player.clone();
-    let system: System = game_state.systems[target_system as usize].clone();
-    
-    if !system.has_presence(&current_player) {panic!("Cannot build in a System without presence")}
-    
+    let system: System = game_state.get_system(target_system)?.clone();
+
+    if !system.has_presence(&current_player) {return Err(RuleViolation::NoPresence { system_id: target_system, player: current_player })}
+    if game_state.campaign.as_ref().is_some_and(|c| c.blight_in(target_system).iter().any(|b| b.fresh)) {return Err(RuleViolation::FreshBlight { system_id: target_system })}
+
     match system{
-        System::Unused => panic!("System is unused"),
+        System::Unused => Err(RuleViolation::UnusedSystem { system_id: target_system }),
         System::Used {
             system_id,
             system_type,
@@ -23,39 +25,45 @@ pub fn build(game_state: &GameState, target_system: u8, build_type: BuildType) -
             let build_fresh = match controlled_by.clone() {
                 None => true,
                 Some(c) => c==current_player
-            };    
-        
+            };
+
+            if build_type != BuildType::Ship {
+                if building_slots.is_empty() {return Err(RuleViolation::NoBuildingSlots { system_id: target_system })}
+                if !building_slots.contains(&BuildingSlot::Empty) {return Err(RuleViolation::BuildingSlotsOccupied { system_id: target_system })}
+            }
+
             match build_type {
-                BuildType::Ship => game_state.systems[target_system as usize] = System::Used {
-                    system_id: system_id, 
-                    system_type: system_type.clone(), 
+                BuildType::Ship => game_state.set_system(target_system, System::Used {
+                    system_id: system_id,
+                    system_type: system_type.clone(),
                     building_slots: building_slots.clone(),
-                    ships: place_ships(&ships, &current_player, if build_fresh {1} else {0}, if !build_fresh {0} else {0}), 
-                    controlled_by: controlled_by.clone(), 
-                    connects_to: connects_to.clone() }.use_building(&BuildingType::Starport, &current_player).update_control(),
+                    ships: place_ships(&ships, &current_player, if build_fresh {1} else {0}, if !build_fresh {0} else {0}),
+                    controlled_by: controlled_by.clone(),
+                    connects_to: connects_to.clone() }.use_building(&BuildingType::Starport, &current_player)?),
                 BuildType::City => {
                     let building = BuildingSlot::Occupied {fresh: build_fresh, player: current_player, building_type: BuildingType::City, used: false};
-                    game_state.systems[target_system as usize] = System::Used {
-                    system_id: system_id, 
-                    system_type: system_type.clone(), 
-                    building_slots: place_building(&building_slots, building), 
-                    ships: ships.clone(), 
-                    controlled_by: controlled_by.clone(), 
-                    connects_to: connects_to.clone() }
-                },   
+                    game_state.set_system(target_system, System::Used {
+                    system_id: system_id,
+                    system_type: system_type.clone(),
+                    building_slots: place_building(&building_slots, building),
+                    ships: ships.clone(),
+                    controlled_by: controlled_by.clone(),
+                    connects_to: connects_to.clone() });
+                    return game_state.change_cities_in_reserve(&game_state.current_player.clone(), -1);
+                },
                 BuildType::Starport => {
                     let building = BuildingSlot::Occupied {fresh: build_fresh, player: current_player, building_type: BuildingType::Starport, used: false};
-                    game_state.systems[target_system as usize] = System::Used {
-                    system_id: system_id, 
-                    system_type: system_type.clone(), 
-                    building_slots: place_building(&building_slots, building), 
-                    ships: ships.clone(), 
-                    controlled_by: controlled_by.clone(), 
-                    connects_to: connects_to.clone() }
+                    game_state.set_system(target_system, System::Used {
+                    system_id: system_id,
+                    system_type: system_type.clone(),
+                    building_slots: place_building(&building_slots, building),
+                    ships: ships.clone(),
+                    controlled_by: controlled_by.clone(),
+                    connects_to: connects_to.clone() })
                 }
             }
-            return game_state;
+            return Ok(game_state);
         }
     }
-    
-}
\ No newline at end of file
+
+}
@@ -2,8 +2,11 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 use crate::data::game_state::{Agents, AmbitionTypes, Color, GameState, PlayerArea, ReserveType, ResourceType, Trophy, TurnState};
+use crate::data::campaign;
+use crate::data::events::GameEvent;
+use crate::data::violations::RuleViolation;
 
-pub(crate) fn score_ambition(game_state: &GameState, ambition: AmbitionTypes) -> GameState {
+pub(crate) fn score_ambition(game_state: &GameState, ambition: AmbitionTypes) -> Result<GameState, RuleViolation> {
     let evaluation = match ambition {
         AmbitionTypes::Tycoon => |a: PlayerArea| a.get_resource_count(ResourceType::Fuel) + a.get_resource_count(ResourceType::Material),
         AmbitionTypes::Tyrant => |a: PlayerArea| a.get_captives(),
@@ -62,21 +65,27 @@ pub(crate) fn score_ambition(game_state: &GameState, ambition: AmbitionTypes) ->
         .iter()
         .map(|(c,a)| (c.clone(), PlayerArea { power: a.power + *points.get(&Some(c.clone())).unwrap() as u8, ..a.clone()}))
         .collect();
-    
+    let scored_points = game_state.seating.iter().map(|c| (c.clone(), *points.get(&Some(c.clone())).unwrap() as u8)).collect();
+    let scored_game_state = GameState {
+        players: new_players,
+        events: game_state.events.iter().cloned().chain(vec![GameEvent::AmbitionScored { ambition: ambition.clone(), points: scored_points }]).collect(),
+        .. game_state.clone()
+    };
+
     if ambition == AmbitionTypes::Warlord {
         let trophies: Vec<Trophy> = game_state.players
             .iter()
-            .map(|(_,a)| a.tropies.clone())
+            .map(|(_,a)| a.trophies.clone())
             .flatten()
             .collect();
         
-        let mut new_game_state = game_state.clone();
+        let mut new_game_state = scored_game_state;
 
         for t in trophies {
-            new_game_state.update_players_reserve(&t.player, &t.trophy_type, t.count as i8);
+            new_game_state.update_players_reserve(&t.player, &t.trophy_type, t.count as i8)?;
         }
 
-        return new_game_state;
+        return Ok(new_game_state);
         
     }
 
@@ -86,38 +95,51 @@ pub(crate) fn score_ambition(game_state: &GameState, ambition: AmbitionTypes) ->
             .map(|(_,a)| a.captives.clone())
             .flatten()
             .collect();
-        let mut new_game_state = game_state.clone();
+        let mut new_game_state = scored_game_state;
 
         for a in captives {
-            new_game_state.update_players_reserve(&a.color, &ReserveType::Agents, a.count as i8);
+            new_game_state.update_players_reserve(&a.color, &ReserveType::Agents, a.count as i8)?;
         }
 
-        return new_game_state;
+        return Ok(new_game_state);
     }
 
-    GameState {players: new_players, .. game_state.clone()}
+    Ok(scored_game_state)
 }
 
-pub(crate) fn end_chapter(game_state: &GameState) -> GameState {
+pub(crate) fn end_chapter(game_state: &GameState) -> Result<GameState, RuleViolation> {
     let new_game_state = game_state.ambitions.iter()
         .filter(|(_, a)| a.markers.len() != 0)
         .map(|(t, a)| (t.clone(), a.clone()))
-        .fold(game_state.clone(), |gs, (ambition_type, _)| {
+        .try_fold(game_state.clone(), |gs, (ambition_type, _)| {
             score_ambition(&gs, ambition_type.clone())
-        });
+        })?;
 
     //Todo reshuffle cards
+    let mut new_game_state = campaign::check_objectives(&new_game_state);
+    new_game_state.events.push(GameEvent::ChapterEnded { chapter: new_game_state.chapter });
+    new_game_state.chapter += 1;
+
+    return Ok(check_game_over(&new_game_state));
+}
+
+// Once the game is over nothing can be played anymore, the players with the most power win
+fn check_game_over(game_state: &GameState) -> GameState {
+    if !game_state.is_game_over() {return game_state.clone()}
+    let most_power = game_state.players.values().map(|a| a.power).max().unwrap_or(0);
+    let winners = game_state.seating.iter().filter(|c| game_state.get_player_area(c).power == most_power).cloned().collect();
 
-    println!("End Chapter");
-    return new_game_state;
+    let mut new_game_state = game_state.clone();
+    new_game_state.turn_state = TurnState::GameOver;
+    new_game_state.events.push(GameEvent::GameEnded { winners: winners });
+    new_game_state
 }
 
-pub(crate) fn end_round(game_state: &GameState) -> GameState {
-    println!("Ending Round");
+pub(crate) fn end_round(game_state: &GameState) -> Result<GameState, RuleViolation> {
     //determine new Initiative, discard Cards
     let mut new_game_state = game_state.clone();
 
-    let lead = game_state.lead_card.as_ref().unwrap();
+    let lead = game_state.lead_card.as_ref().ok_or(RuleViolation::NoLeadCard)?;
     let follow_cards = game_state.follow_cards.clone();
 
     new_game_state.initiative =  match new_game_state.seized.clone() {
@@ -131,24 +153,25 @@ pub(crate) fn end_round(game_state: &GameState) -> GameState {
 
     new_game_state.action_discard.push(lead.0.clone());
     new_game_state.action_discard = new_game_state.action_discard.iter().cloned().chain(follow_cards.iter().map(|(c, _, _)| c.clone())).collect();
+    new_game_state.lead_card = None;
     new_game_state.follow_cards = vec![];
     new_game_state.turn_state = TurnState::TrickTaking;
     new_game_state.players_in_round = new_game_state.players.iter().filter(|(_, a)| a.action_cards.len() != 0).count() as u8;
+    new_game_state.events.push(GameEvent::RoundEnded { initiative: new_game_state.initiative.clone() });
 
-    if new_game_state.players.iter().filter(|(_, area)| area.action_cards.len() != 0).count() == 0 {end_chapter(&new_game_state)} else {new_game_state}
+    if new_game_state.players.iter().filter(|(_, area)| area.action_cards.len() != 0).count() == 0 {end_chapter(&new_game_state)} else {Ok(new_game_state)}
 }
 
-pub(crate) fn end_turn(game_state: &GameState) -> GameState {
-    let player_order: Vec<Color> = vec![Color::Red, Color::Blue, Color::White, Color::Yellow].iter().take(game_state.players.len()).cloned().collect();
+pub(crate) fn end_turn(game_state: &GameState) -> Result<GameState, RuleViolation> {
     let mut new_game_state = game_state.clone();
     new_game_state.turn_state = TurnState::TrickTaking;
-    new_game_state.current_player = player_order[(player_order.iter().position(|c| *c == new_game_state.current_player).unwrap() + 1)%player_order.len()].clone();
-    new_game_state.systems = new_game_state.systems.iter().map(|s| s.refresh_buildings()).collect();
+    new_game_state.current_player = game_state.next_player(&game_state.current_player);
+    new_game_state.update_systems(|s| s.refresh_buildings());
 
     // last player in Turn Order
     if (1 + game_state.follow_cards.len()) as u8 == game_state.players_in_round {
         return end_round(&new_game_state);
     } else {
-        new_game_state
+        Ok(new_game_state)
     }
 }
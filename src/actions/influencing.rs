@@ -1,11 +1,12 @@
 use crate::data::game_state::{GameState, Agents, ReserveType};
 use crate::data::court_cards::CourtCard;
+use crate::data::violations::RuleViolation;
 
-pub(crate) fn influence(game_state: &GameState, target_card: u8) -> GameState {
+pub(crate) fn influence(game_state: &GameState, target_card: u8) -> Result<GameState, RuleViolation> {
     let current_player = game_state.current_player.clone();
     let mut new_game_state = game_state.clone();
 
-    new_game_state.court[target_card as usize] = match new_game_state.court[target_card as usize].clone() {
+    new_game_state.court[target_card as usize] = match new_game_state.court.get(target_card as usize).cloned().ok_or(RuleViolation::UnknownCourtCard { card_id: target_card })? {
         CourtCard::VoxCard { vox, agents } => CourtCard::VoxCard {vox: vox, agents: agents.iter().map(|a| {
             if a.color == current_player {Agents{color: current_player.clone(), count: a.count + 1}}
             else {a.clone()}
@@ -16,7 +17,7 @@ pub(crate) fn influence(game_state: &GameState, target_card: u8) -> GameState {
         }).collect()},
     };
 
-    new_game_state.update_players_reserve(&current_player, &ReserveType::Agents, -1);
+    new_game_state.update_players_reserve(&current_player, &ReserveType::Agents, -1)?;
 
-    return new_game_state;
+    return Ok(new_game_state);
 }
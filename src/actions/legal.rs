@@ -0,0 +1,256 @@
+use std::collections::{HashMap, VecDeque};
+
+use itertools::{iproduct, Itertools};
+
+use crate::data::court_cards::{CourtCard, VoxPayload};
+use crate::data::game_state::{Action, BasicAction, BuildType, Color, Dice, GameState, ReserveType, ResourceSlot, ResourceType, TurnState};
+use crate::data::legal_actions::{ShipMoveOptions, ShipMoves};
+use crate::data::reach::Reach;
+use crate::data::system::{BuildingSlot, BuildingType, System};
+
+use super::moving::{catapult_destinations, passable_gates};
+use super::{card_allows, powers, prelude_resource_allowed};
+
+// Candidates for every action of the current player, equivalent actions are listed once:
+// every origin sends one fleet along the shortest passable path, and systems, slots and dice are sorted.
+// Fleet moves and catapults are grouped by their limits, each group is checked with its largest member.
+pub(crate) fn candidate_actions(game_state: &GameState) -> (Vec<Action>, Vec<ShipMoveOptions>) {
+    match &game_state.turn_state {
+        TurnState::TrickTaking => (trick_taking_actions(game_state), vec![]),
+        TurnState::Prelude { .. } => {
+            let Some((lead, _, _)) = &game_state.lead_card else {return (vec![], vec![])};
+            let player = &game_state.current_player;
+            let player_area = game_state.get_player_area(player);
+            let allowed = |b: &BasicAction, resource: &ResourceType| powers::prelude_resource(game_state, player, b, Some(resource.clone())).is_some_and(|r| prelude_resource_allowed(b, &r, &lead.action_type));
+            let resources: Vec<(u8, ResourceType)> = (0..player_area.resource_slots.len() as u8).filter_map(|slot| player_area.get_resource(slot).map(|r| (slot, r))).collect();
+            let basic_actions = basic_actions(game_state);
+            let ship_moves = ship_moves(game_state);
+
+            let resource_actions = resources.iter()
+                .flat_map(|(slot, resource)| basic_actions.iter()
+                    .filter(|b| allowed(b, resource))
+                    .map(|b| Action::PreludeResourceAction { basic_action: b.clone(), used_resource: *slot })
+                    .collect::<Vec<_>>());
+            let ship_move_options = resources.iter()
+                .flat_map(|(slot, resource)| ship_moves.iter()
+                    .filter(|m| allowed(&largest_move(m), resource))
+                    .map(|m| ShipMoveOptions { used_resource: Some(*slot), moves: m.clone() })
+                    .collect::<Vec<_>>());
+            (vec![Action::EndPrelude].into_iter().chain(resource_actions).collect(), ship_move_options.collect())
+        },
+        TurnState::Actions { action_type, pips_left } => {
+            if *pips_left == 0 {return (vec![Action::EndTurn], vec![])}
+            let main_actions = basic_actions(game_state).into_iter()
+                .filter(|b| card_allows(action_type, b))
+                .map(|b| Action::MainAction { basic_action: b });
+            let ship_move_options = ship_moves(game_state).into_iter()
+                .filter(|m| card_allows(action_type, &largest_move(m)))
+                .map(|m| ShipMoveOptions { used_resource: None, moves: m });
+            (vec![Action::EndTurn].into_iter().chain(main_actions).collect(), ship_move_options.collect())
+        },
+        TurnState::AllocateResources { player, resources } => (allocations(game_state, player, resources)
+            .into_iter()
+            .map(|c| Action::AllocateResources { configuration: c })
+            .collect(), vec![]),
+        TurnState::AllocateDiceResults { .. } | TurnState::GameOver => (vec![], vec![])
+    }
+}
+
+// Moves every ship a group allows, if this is legal so is every smaller move of the group
+pub(crate) fn largest_move(ship_moves: &ShipMoves) -> BasicAction {
+    match ship_moves {
+        ShipMoves::Fleets { destination_id, fleets, .. } => BasicAction::MoveFleets { destination_id: *destination_id, fleets: fleets.clone() },
+        ShipMoves::Catapult { origin_system, fresh, damaged, gates, planets } => BasicAction::Catapult {
+            origin_system: *origin_system,
+            destination_systems: vec![(*gates.first().or(planets.first()).unwrap(), *fresh, *damaged)]
+        }
+    }
+}
+
+fn trick_taking_actions(game_state: &GameState) -> Vec<Action> {
+    let hand = game_state.get_player_area(&game_state.current_player).action_cards;
+    match &game_state.lead_card {
+        None => hand.iter()
+            .flat_map(|card| vec![None, card.declared_ambition.clone()].into_iter().unique().map(|declare| Action::PlayLeadCard { card: card.clone(), declare: declare }))
+            .collect(),
+        Some(_) => hand.iter().enumerate()
+            .flat_map(|(i, card)| {
+                let seize_options: Vec<Option<_>> = vec![None].into_iter().chain(hand.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, c)| Some(c.clone())))
+                    .fold(vec![], |options, seize| if options.contains(&seize) {options} else {options.into_iter().chain(vec![seize]).collect()});
+                seize_options.into_iter().flat_map(|seize| vec![
+                    Action::Surpass { card: card.clone(), seize: seize.clone() },
+                    Action::Copy { card: card.clone(), seize: seize.clone() },
+                    Action::Pivot { card: card.clone(), seize: seize }
+                ]).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+fn basic_actions(game_state: &GameState) -> Vec<BasicAction> {
+    let player = &game_state.current_player;
+    let systems = game_state.reach.systems();
+    let build_types = [BuildType::Ship, BuildType::City, BuildType::Starport];
+
+    let builds = iproduct!(systems.iter(), build_types.iter()).flat_map(|(s, b)| vec![
+        BasicAction::Build { target_system: *s, build_type: b.clone() },
+        BasicAction::Repair { target_system: *s, build_type: b.clone() }
+    ]);
+    let taxes = iproduct!(systems.iter(), game_state.seating.iter()).map(|(s, p)| BasicAction::Tax { target_system: *s, target_player: p.clone() });
+    let influences = (0..game_state.court.len() as u8).map(|i| BasicAction::Influence { card_id: i });
+    let secures = (0..game_state.court.len() as u8).flat_map(|i| match &game_state.court[i as usize] {
+        CourtCard::GuildCard { .. } => vec![BasicAction::Secure { card_id: i, vox_payload: None }],
+        CourtCard::VoxCard { .. } => vox_payloads(game_state).into_iter().map(|p| BasicAction::Secure { card_id: i, vox_payload: Some(p) }).collect()
+    });
+
+    builds
+        .chain(taxes)
+        .chain(influences)
+        .chain(secures)
+        .chain(moves(game_state, player))
+        .chain(battles(game_state, player))
+        .collect()
+}
+
+fn ships_of(game_state: &GameState, system_id: u8, player: &Color) -> (u8, u8) {
+    match &game_state.systems[system_id as usize] {
+        System::Used { ships, .. } => ships.get(player).map(|s| (s.fresh, s.damaged)).unwrap_or((0, 0)),
+        System::Unused => (0, 0)
+    }
+}
+
+// Every split of the ships in a system into fresh and damaged ships moving on, including moving none
+fn ship_splits(fresh: u8, damaged: u8) -> Vec<(u8, u8)> {
+    iproduct!(0..=fresh, 0..=damaged).collect()
+}
+
+fn moves(game_state: &GameState, player: &Color) -> Vec<BasicAction> {
+    game_state.reach.systems().into_iter()
+        .flat_map(|origin| {
+            let (fresh, damaged) = ships_of(game_state, origin, player);
+            game_state.reach.neighbours(origin).iter()
+                .flat_map(|destination| ship_splits(fresh, damaged).into_iter()
+                    .filter(|(f, d)| f + d > 0)
+                    .map(|(f, d)| BasicAction::Move { origin_id: origin, destination_id: *destination, fresh_ships: f, damaged_ships: d }))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+// Path from origin up to the last system before destination, passing only gates not controlled by a rival
+fn fleet_path(game_state: &GameState, origin: u8, destination: u8, passable: &impl Fn(u8) -> bool) -> Option<Vec<u8>> {
+    let mut previous: HashMap<u8, u8> = HashMap::new();
+    let mut queue = VecDeque::from(vec![origin]);
+    while let Some(current) = queue.pop_front() {
+        for next in game_state.reach.neighbours(current) {
+            if *next == origin || previous.contains_key(next) {continue;}
+            previous.insert(*next, current);
+            if *next == destination {
+                let mut path = vec![current];
+                while *path.last().unwrap() != origin {path.push(previous[path.last().unwrap()]);}
+                path.reverse();
+                return Some(path);
+            }
+            if Reach::is_gate(*next) && passable(*next) {queue.push_back(*next);}
+        }
+    }
+    None
+}
+
+// Fleets from every origin, which can reach the destination
+fn fleet_moves(game_state: &GameState, player: &Color) -> Vec<ShipMoves> {
+    let passable = passable_gates(game_state, player);
+    let origins: Vec<u8> = game_state.reach.systems().into_iter().filter(|s| ships_of(game_state, *s, player) != (0, 0)).collect();
+    let gates: Vec<(u8, Vec<u8>)> = game_state.reach.gates().into_iter().filter(|g| passable(*g)).map(|g| (g, game_state.reach.neighbours(g).clone())).collect();
+
+    game_state.reach.systems().into_iter()
+        .filter_map(|destination| {
+            let fleets: Vec<(Vec<u8>, u8, u8)> = origins.iter()
+                .filter(|o| **o != destination)
+                .filter_map(|o| fleet_path(game_state, *o, destination, &passable))
+                .map(|path| {
+                    let (fresh, damaged) = ships_of(game_state, path[0], player);
+                    (path, fresh, damaged)
+                })
+                .collect();
+            if fleets.is_empty() {return None}
+            Some(ShipMoves::Fleets { destination_id: destination, fleets: fleets, gates: gates.clone() })
+        })
+        .collect()
+}
+
+fn catapults(game_state: &GameState, player: &Color) -> Vec<ShipMoves> {
+    game_state.reach.systems().into_iter()
+        .filter(|origin| match &game_state.systems[*origin as usize] {
+            System::Used { building_slots, .. } => building_slots.iter().any(|b| matches!(b, BuildingSlot::Occupied { player: p, building_type: BuildingType::Starport, .. } if p == player)),
+            System::Unused => false
+        })
+        .filter_map(|origin| {
+            let (fresh, damaged) = ships_of(game_state, origin, player);
+            let destinations = catapult_destinations(game_state, origin, player);
+            if (fresh, damaged) == (0, 0) || destinations.is_empty() {return None}
+            let (gates, planets): (Vec<u8>, Vec<u8>) = destinations.into_iter().partition(|s| Reach::is_gate(*s));
+            Some(ShipMoves::Catapult { origin_system: origin, fresh: fresh, damaged: damaged, gates: gates, planets: planets })
+        })
+        .collect()
+}
+
+fn ship_moves(game_state: &GameState) -> Vec<ShipMoves> {
+    let player = &game_state.current_player;
+    fleet_moves(game_state, player).into_iter().chain(catapults(game_state, player)).collect()
+}
+
+fn battles(game_state: &GameState, player: &Color) -> Vec<BasicAction> {
+    let dice = [Dice::Skirmish, Dice::Assault, Dice::Raid];
+    game_state.reach.systems().into_iter()
+        .flat_map(|system| {
+            let ships = game_state.systems[system as usize].get_all_ships(player);
+            iproduct!(game_state.seating.iter().filter(|p| *p != player), 1..=ships)
+                .flat_map(|(target, count)| dice.iter().cloned().combinations_with_replacement(count as usize)
+                    .map(|d| BasicAction::Battle { target_system: system, target_player: target.clone(), dice: d })
+                    .collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+// Mass Uprising places one ship into each system of a cluster, or as many as the reserve allows
+fn vox_payloads(game_state: &GameState) -> Vec<VoxPayload> {
+    let reserve_ships = *game_state.get_player_area(&game_state.current_player).reserve.get(&ReserveType::Ships).unwrap_or(&0);
+    game_state.reach.clusters.iter()
+        .flat_map(|c| c.systems_id.iter().cloned().combinations((reserve_ships as usize).min(c.systems_id.len())))
+        .unique()
+        .map(|systems| VoxPayload::MassUprising { target_systems: systems })
+        .collect()
+}
+
+// Keeps any selection of the held and gained resources in the slots, which are not covered
+fn allocations(game_state: &GameState, player: &Color, resources: &[ResourceType]) -> Vec<Vec<(u8, ResourceType)>> {
+    let slots = game_state.get_player_area(player).resource_slots;
+    let available = slots.iter()
+        .filter_map(|s| match s {
+            ResourceSlot::Used { resource, .. } => Some(resource.clone()),
+            _ => None
+        })
+        .chain(resources.iter().cloned())
+        .counts();
+    let open_slots: Vec<u8> = slots.iter().enumerate().filter(|(_, s)| !matches!(s, ResourceSlot::Covered { .. })).map(|(i, _)| i as u8).collect();
+
+    open_slots.iter().fold(vec![(vec![], available)], |configurations, slot| {
+        configurations.into_iter().flat_map(|(configuration, left)| {
+            let keep_empty = (configuration.clone(), left.clone());
+            let fill = left.iter()
+                .filter(|(_, count)| **count > 0)
+                .map(|(resource, _)| {
+                    let mut left = left.clone();
+                    *left.get_mut(resource).unwrap() -= 1;
+                    (configuration.iter().cloned().chain(vec![(*slot, resource.clone())]).collect::<Vec<_>>(), left)
+                })
+                .collect::<Vec<_>>();
+            vec![keep_empty].into_iter().chain(fill)
+        }).collect()
+    })
+    .into_iter()
+    .map(|(configuration, _)| configuration)
+    .collect()
+}
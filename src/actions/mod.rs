@@ -2,7 +2,11 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 
+use crate::data::events::GameEvent;
 use crate::data::game_state::{Action, ActionType, BasicAction, Color, GameState, PlayerArea, ResourceSlot, ResourceType, TurnState};
+use crate::data::legal_actions::LegalActions;
+use crate::data::preview::ActionPreview;
+use crate::data::violations::RuleViolation;
 
 mod building;
 mod tricktaking;
@@ -13,6 +17,8 @@ mod battling;
 mod securing;
 mod taxing;
 mod allocating;
+mod powers;
+mod legal;
 pub mod moving;
 
 fn use_action_pip(game_state: &GameState) -> GameState {
@@ -26,32 +32,94 @@ fn use_action_pip(game_state: &GameState) -> GameState {
     }
 }
 
-fn execute_prelude_action(game_state: &GameState, action: BasicAction, resource: Option<ResourceType>) -> GameState {
-    match (action.clone(), resource.clone().expect("No Resource in ResourceSlot"), game_state.lead_card.clone().unwrap().0.action_type) {
-        (BasicAction::Build { target_system, build_type }, ResourceType::Material, _) => building::build(game_state, target_system, build_type),
-        (BasicAction::Build { target_system, build_type }, ResourceType::Psionics, ActionType::Construction) => building::build(game_state, target_system, build_type),
-        (BasicAction::Repair { target_system, build_type }, ResourceType::Material, _) => repairing::repair(game_state, target_system, build_type),
-        (BasicAction::Repair { target_system, build_type }, ResourceType::Psionics, ActionType::Mobilization) => repairing::repair(game_state, target_system, build_type),
-        (BasicAction::Tax { target_system, target_player }, ResourceType::Psionics, ActionType::Administration) => taxing::tax(game_state, target_system, target_player),
-        (BasicAction::Influence { card_id }, ResourceType::Psionics, ActionType::Administration | ActionType::Mobilization) => influencing::influence(game_state, card_id),
-        (BasicAction::Move { origin_id, destination_id, fresh_ships, damaged_ships }, ResourceType::Fuel, _) => moving::move_ships(game_state, origin_id, destination_id, fresh_ships, damaged_ships),
-        (BasicAction::Move { origin_id, destination_id, fresh_ships, damaged_ships }, ResourceType::Psionics, ActionType::Agression | ActionType::Mobilization) => moving::move_ships(game_state, origin_id, destination_id, fresh_ships, damaged_ships),
-        (BasicAction::Catapult { origin_system, destination_systems }, ResourceType::Fuel, _) => moving::catapult(game_state, origin_system, destination_systems),
-        (BasicAction::Catapult { origin_system, destination_systems }, ResourceType::Psionics, ActionType::Agression | ActionType::Mobilization) => moving::catapult(game_state, origin_system, destination_systems),
-        (BasicAction::Secure { card_id, vox_payload }, ResourceType::Relics, _) => securing::secure(game_state, card_id, vox_payload),
-        (BasicAction::Secure { card_id, vox_payload }, ResourceType::Psionics, ActionType::Agression) => securing::secure(game_state, card_id, vox_payload),
-        (BasicAction::Battle { target_system, target_player, dice }, ResourceType::Psionics, ActionType::Agression) => battling::battle(game_state, target_system, target_player, dice),
-        _ => panic!("Cannot execute {:?} with {:?} resource and {:?} lead", action.clone(), resource.clone(), game_state.lead_card.clone().unwrap().0.action_type)
+// Resources paying for a prelude action, Psionics pay for any action of the lead card
+fn prelude_resource_allowed(action: &BasicAction, resource: &ResourceType, lead: &ActionType) -> bool {
+    match (action, resource, lead) {
+        (BasicAction::Build { .. }, ResourceType::Material, _) => true,
+        (BasicAction::Build { .. }, ResourceType::Psionics, ActionType::Construction) => true,
+        (BasicAction::Repair { .. }, ResourceType::Material, _) => true,
+        (BasicAction::Repair { .. }, ResourceType::Psionics, ActionType::Mobilization) => true,
+        (BasicAction::Tax { .. }, ResourceType::Psionics, ActionType::Administration) => true,
+        (BasicAction::Influence { .. }, ResourceType::Psionics, ActionType::Administration | ActionType::Mobilization) => true,
+        (BasicAction::Move { .. } | BasicAction::MoveFleets { .. } | BasicAction::Catapult { .. }, ResourceType::Fuel, _) => true,
+        (BasicAction::Move { .. } | BasicAction::MoveFleets { .. } | BasicAction::Catapult { .. }, ResourceType::Psionics, ActionType::Agression | ActionType::Mobilization) => true,
+        (BasicAction::Secure { .. }, ResourceType::Relics, _) => true,
+        (BasicAction::Secure { .. }, ResourceType::Psionics, ActionType::Agression) => true,
+        (BasicAction::Battle { .. }, ResourceType::Psionics, ActionType::Agression) => true,
+        _ => false
     }
 }
 
-pub fn execute_actions(game_state: &GameState, actions: Vec<Action>) -> GameState {
+// Main actions printed on the action cards of each type
+fn card_allows(action_type: &ActionType, action: &BasicAction) -> bool {
+    match (action_type, action) {
+        (ActionType::Administration, BasicAction::Repair { .. } | BasicAction::Tax { .. } | BasicAction::Influence { .. }) => true,
+        (ActionType::Agression, BasicAction::Move { .. } | BasicAction::MoveFleets { .. } | BasicAction::Catapult { .. } | BasicAction::Secure { .. } | BasicAction::Battle { .. }) => true,
+        (ActionType::Construction, BasicAction::Build { .. } | BasicAction::Repair { .. }) => true,
+        (ActionType::Mobilization, BasicAction::Move { .. } | BasicAction::MoveFleets { .. } | BasicAction::Catapult { .. } | BasicAction::Influence { .. }) => true,
+        _ => false
+    }
+}
+
+fn execute_basic_action(game_state: &GameState, action: BasicAction) -> Result<GameState, RuleViolation> {
+    match action {
+        BasicAction::Build { target_system, build_type } => building::build(game_state, target_system, build_type),
+        BasicAction::Repair { target_system, build_type } => repairing::repair(game_state, target_system, build_type),
+        BasicAction::Tax { target_system, target_player } => taxing::tax(game_state, target_system, target_player),
+        BasicAction::Influence { card_id } => influencing::influence(game_state, card_id),
+        BasicAction::Move { origin_id, destination_id, fresh_ships, damaged_ships } => moving::move_ships(game_state, origin_id, destination_id, fresh_ships, damaged_ships),
+        BasicAction::MoveFleets { destination_id, fleets } => moving::move_fleets(game_state, destination_id, fleets),
+        BasicAction::Catapult { origin_system, destination_systems } => moving::catapult(game_state, origin_system, destination_systems),
+        BasicAction::Secure { card_id, vox_payload } => securing::secure(game_state, card_id, vox_payload),
+        BasicAction::Battle { target_system, target_player, dice } => battling::battle(game_state, target_system, target_player, dice)
+    }
+}
+
+fn execute_prelude_action(game_state: &GameState, action: BasicAction, resource: Option<ResourceType>) -> Result<GameState, RuleViolation> {
+    let lead = game_state.lead_card.clone().ok_or(RuleViolation::NoLeadCard)?.0.action_type;
+    if !resource.as_ref().is_some_and(|r| prelude_resource_allowed(&action, r, &lead)) {
+        return Err(RuleViolation::WrongPreludeResource { action: action, resource: resource, lead: lead })
+    }
+    execute_basic_action(game_state, action)
+}
+
+// Every action the current player may take now, each candidate is checked by executing it
+pub fn legal_actions(game_state: &GameState) -> LegalActions {
+    let (actions, ship_moves) = legal::candidate_actions(game_state);
+    LegalActions {
+        actions: actions.into_iter().filter(|a| execute_action(game_state, a.clone()).is_ok()).collect(),
+        ship_moves: ship_moves.into_iter().filter(|o| execute_action(game_state, o.action(legal::largest_move(&o.moves))).is_ok()).collect()
+    }
+}
+
+// Checks an action without applying it, the effects are predicted by executing it on the unchanged game state
+pub fn validate_action(game_state: &GameState, action: Action) -> Result<ActionPreview, RuleViolation> {
+    execute_action(game_state, action).map(|new_game_state| ActionPreview::between(game_state, &new_game_state))
+}
+
+pub fn execute_actions(game_state: &GameState, actions: Vec<Action>) -> Result<GameState, RuleViolation> {
     return actions
         .iter()
-        .fold(game_state.clone(), |g, action| execute_action(&g, action.clone()));
+        .try_fold(game_state.clone(), |g, action| execute_action(&g, action.clone()));
+}
+
+pub fn execute_action(game_state: &GameState, action: Action) -> Result<GameState, RuleViolation> {
+    let description = if cfg!(debug_assertions) {format!("{:?}", action)} else {String::new()};
+    let mut logged_game_state = game_state.clone();
+    logged_game_state.events.push(GameEvent::ActionTaken { player: game_state.current_player.clone(), action: action.clone() });
+    let new_game_state = apply_action(&logged_game_state, action.clone())?;
+    let mut new_game_state = powers::after_action(&new_game_state, &game_state.current_player, &action)?;
+    debug_assert!(new_game_state.stale_control().is_empty(), "Control of Systems {:?} is stale after {}", new_game_state.stale_control(), description);
+
+    // Resources and control change in many places, so they are logged by comparing the states
+    let changes = ActionPreview::between(game_state, &new_game_state);
+    new_game_state.events.extend(changes.resources.into_iter().map(|r| GameEvent::ResourceChanged { player: r.player, resource: r.resource, change: r.change }));
+    new_game_state.events.extend(changes.control.into_iter().map(|c| GameEvent::ControlChanged { system_id: c.system_id, before: c.before, after: c.after }));
+    Ok(new_game_state)
 }
 
-pub fn execute_action(game_state: &GameState, action: Action) -> GameState {
+fn apply_action(game_state: &GameState, action: Action) -> Result<GameState, RuleViolation> {
+    let wrong_turn_state = |action: Action| Err(RuleViolation::WrongTurnState { turn_state: game_state.turn_state.clone(), action: action });
     match &game_state.turn_state {
         TurnState::TrickTaking => {
             match action {
@@ -59,7 +127,7 @@ pub fn execute_action(game_state: &GameState, action: Action) -> GameState {
                 Action::Surpass { card, seize } => tricktaking::surpass(game_state, card, seize),
                 Action::Copy { card, seize } => tricktaking::copy(game_state, card, seize),
                 Action::Pivot { card, seize } => tricktaking::pivot(game_state, card, seize),
-                _ => panic!("Can only Execute TrickTaking Actions")
+                _ => wrong_turn_state(action)
             }
         },
         TurnState::Prelude { action_type, pips_left } => {
@@ -67,54 +135,32 @@ pub fn execute_action(game_state: &GameState, action: Action) -> GameState {
                 Action::EndPrelude => {
                     let mut new_game_state = game_state.clone();
                     new_game_state.turn_state = TurnState::Actions { action_type: action_type.clone(), pips_left: pips_left.clone() };
-                    new_game_state
+                    Ok(new_game_state)
                 },
                 Action::PreludeResourceAction { basic_action, used_resource } => execute_prelude_action(
                     game_state,
-                    basic_action,
-                    game_state.players.get(&game_state.current_player).unwrap().get_resource(used_resource)
+                    basic_action.clone(),
+                    powers::prelude_resource(game_state, &game_state.current_player, &basic_action, game_state.players.get(&game_state.current_player).unwrap().get_resource(used_resource))
                 ),
-                Action::UseWeapons { used_resource } => todo!(),
-                Action::PreludeCard { guild_card, prelude_action_payload } => todo!(),
-                _ => panic!("Cannot execute {:?} in Prelude", action)
+                Action::UseWeapons { .. } | Action::PreludeCard { .. } => Err(RuleViolation::NotImplemented { action: action }),
+                _ => wrong_turn_state(action)
             }
         },
         TurnState::Actions { action_type, pips_left } => {
             if action == Action::EndTurn {return ending::end_turn(game_state)}
-            if *pips_left == 0 {panic!("No Action pips left in {:?}, when executing {:?}", game_state.turn_state, action)}
-            return match (action_type, action) {
-                (ActionType::Administration, Action::MainAction { basic_action }) => match basic_action {
-                    BasicAction::Repair { target_system, build_type } => repairing::repair(&use_action_pip(&game_state), target_system, build_type),
-                    BasicAction::Tax { target_system, target_player } => taxing::tax(&use_action_pip(&game_state), target_system, target_player),
-                    BasicAction::Influence { card_id } => influencing::influence(&use_action_pip(&game_state), card_id),
-                    _ => panic!("Cannot execute Action with Administration Action Card")                    
-                },
-                (ActionType::Agression, Action::MainAction { basic_action }) => match basic_action {
-                    BasicAction::Move { origin_id, destination_id, fresh_ships, damaged_ships } => moving::move_ships(&use_action_pip(&game_state), origin_id, destination_id, fresh_ships, damaged_ships),
-                    BasicAction::Catapult { origin_system, destination_systems } => moving::catapult(game_state, origin_system, destination_systems),
-                    BasicAction::Secure { card_id , vox_payload} => securing::secure(&use_action_pip(&game_state), card_id, vox_payload),
-                    BasicAction::Battle { target_system, target_player, dice } => battling::battle(&use_action_pip(&game_state), target_system, target_player, dice),
-                    _ => panic!("Cannot execute Action with Aggresion Action Card")
-                },
-                (ActionType::Construction, Action::MainAction { basic_action }) => match basic_action {
-                    BasicAction::Build { target_system, build_type } => building::build(&use_action_pip(game_state), target_system, build_type),
-                    BasicAction::Repair { target_system, build_type } => repairing::repair(&use_action_pip(&game_state), target_system, build_type),
-                    _ => panic!("Cannot execute Action with Aggresion Action Card")                    
-                },
-                (ActionType::Mobilization, Action::MainAction { basic_action }) => match basic_action {
-                    BasicAction::Move { origin_id, destination_id, fresh_ships, damaged_ships } => moving::move_ships(&use_action_pip(&game_state), origin_id, destination_id, fresh_ships, damaged_ships),
-                    BasicAction::Catapult { origin_system, destination_systems } => moving::catapult(game_state, origin_system, destination_systems),
-                    BasicAction::Influence { card_id } => influencing::influence(&use_action_pip(&game_state), card_id),
-                    _ => panic!("Cannot execute Action with Mobilization Action Card")                    
-                },
-                (_ , Action::EndTurn) => todo!(),
-                _ => panic!("")
-            }
+            if *pips_left == 0 {return Err(RuleViolation::NoActionPips { turn_state: game_state.turn_state.clone() })}
+            let basic_action = match action {
+                Action::MainAction { basic_action } => basic_action,
+                _ => return wrong_turn_state(action)
+            };
+            if !card_allows(action_type, &basic_action) {return Err(RuleViolation::NotOnActionCard { action_type: action_type.clone(), action: basic_action })}
+            execute_basic_action(&use_action_pip(game_state), basic_action)
         }
         TurnState::AllocateResources { ..} => match action {
             Action::AllocateResources { configuration } => allocating::allocate_resources(game_state, configuration),
-            _ => panic!("Can only AllocateResources in the AllocateResources Turnstate not {:?}", action)
+            _ => wrong_turn_state(action)
         },
-        TurnState::AllocateDiceResults { target_system, target_player, self_hits, hits, building_hits, keys } => todo!(),
+        TurnState::AllocateDiceResults { .. } => Err(RuleViolation::NotImplemented { action: action }),
+        TurnState::GameOver => wrong_turn_state(action)
     }
-}
\ No newline at end of file
+}
@@ -1,15 +1,63 @@
+use itertools::Itertools;
+
 use crate::board::{place_ships, remove_ships};
+use crate::data::events::GameEvent;
 use crate::data::game_state::{Color, GameState};
-use crate::data::system::{BuildingSlot, BuildingType, System, SystemType};
+use crate::data::reach::Reach;
+use crate::data::system::{BuildingSlot, BuildingType, System};
+use crate::data::violations::RuleViolation;
+
+pub fn move_ships(game_state: &GameState, origin_system_id: u8, destination_system_id: u8, fresh: u8, damaged: u8) -> Result<GameState, RuleViolation> {
+    if !game_state.reach.are_connected(origin_system_id, destination_system_id) {return Err(RuleViolation::NotConnected { origin: origin_system_id, destination: destination_system_id })}
+    if fresh == 0 && damaged == 0 {return Err(RuleViolation::NoShipsMoved)}
+    relocate_ships(game_state, origin_system_id, destination_system_id, fresh, damaged)
+}
+
+// Moves several fleets with a single action. Each fleet may pass through gates, which are not controlled by a rival,
+// and all fleets need to end up in the same destination.
+pub fn move_fleets(game_state: &GameState, destination_system_id: u8, fleets: Vec<(Vec<u8>, u8, u8)>) -> Result<GameState, RuleViolation> {
+    let current_player = game_state.current_player.clone();
+    let passable = passable_gates(game_state, &current_player);
+
+    if fleets.is_empty() || fleets.iter().any(|(path, _, _)| path.is_empty()) {return Err(RuleViolation::NoFleets)}
+    if fleets.iter().any(|(_, fresh, damaged)| *fresh == 0 && *damaged == 0) {return Err(RuleViolation::NoShipsMoved)}
 
-pub fn move_ships(game_state: &GameState, origin_system_id: u8, destination_system_id: u8, fresh: u8, damaged: u8) -> GameState {
+    for (path, _, _) in fleets.iter() {
+        let origin = path[0];
+        if !game_state.reach.is_in_play(origin) {return Err(RuleViolation::UnusedSystem { system_id: origin })}
+        if origin == destination_system_id {return Err(RuleViolation::ReturnToOrigin { system_id: origin })}
+        if let Some((from, to)) = path.iter().chain(vec![&destination_system_id]).tuple_windows().find(|(from, to)| !game_state.reach.are_connected(**from, **to)) {
+            return Err(RuleViolation::NotConnected { origin: *from, destination: *to })
+        }
+        if let Some(s) = path.iter().skip(1).find(|s| !Reach::is_gate(**s) || !passable(**s)) {
+            return Err(RuleViolation::GateNotPassable { system_id: *s })
+        }
+    }
+
+    //Check every origin holds the ships of all fleets starting there
+    for (origin, origin_fleets) in fleets.iter().into_group_map_by(|(path, _, _)| path[0]) {
+        // Saturating sums stay above any number of ships present
+        let (fresh, damaged) = origin_fleets.iter().fold((0u8, 0u8), |(f, d), (_, fresh, damaged)| (f.saturating_add(*fresh), d.saturating_add(*damaged)));
+        let ships = match &game_state.systems[origin as usize] {
+            System::Used { ships, .. } => ships.get(&current_player).unwrap().clone(),
+            System::Unused => return Err(RuleViolation::UnusedSystem { system_id: origin })
+        };
+        if fresh > ships.fresh || damaged > ships.damaged {return Err(RuleViolation::NotEnoughShips { system_id: origin, fresh: fresh, damaged: damaged, fresh_present: ships.fresh, damaged_present: ships.damaged })}
+    }
+
+    fleets.iter()
+        .try_fold(game_state.clone(), |g, (path, fresh, damaged)| relocate_ships(&g, path[0], destination_system_id, *fresh, *damaged))
+}
+
+// Takes ships of the current player from origin and places them in destination without checking the way between them
+fn relocate_ships(game_state: &GameState, origin_system_id: u8, destination_system_id: u8, fresh: u8, damaged: u8) -> Result<GameState, RuleViolation> {
     let mut game_state = game_state.clone();
 
     let origin_system = game_state.systems[origin_system_id as usize].clone();
     let destination_system = game_state.systems[destination_system_id as usize].clone();
 
     match origin_system{
-        System::Unused => panic!("Origin system is unused"),
+        System::Unused => return Err(RuleViolation::UnusedSystem { system_id: origin_system_id }),
         System::Used {
             system_id,
             system_type,
@@ -18,21 +66,24 @@ pub fn move_ships(game_state: &GameState, origin_system_id: u8, destination_syst
             controlled_by,
             connects_to
         } => {
-            if !connects_to.contains(&destination_system_id) {panic!("Destination not connected to Origin")}
+            let present = ships.get(&game_state.current_player).unwrap();
+            if fresh > present.fresh || damaged > present.damaged {
+                return Err(RuleViolation::NotEnoughShips { system_id: origin_system_id, fresh: fresh, damaged: damaged, fresh_present: present.fresh, damaged_present: present.damaged })
+            }
             let updated_ships = remove_ships(&ships, &game_state.current_player, fresh, damaged);
-            game_state.systems[origin_system_id as usize] = System::Used {
+            game_state.set_system(origin_system_id, System::Used {
                 system_id,
                 system_type: system_type.clone(),
                 building_slots: building_slots.clone(),
                 ships: updated_ships,
                 controlled_by: controlled_by.clone(),
                 connects_to: connects_to.clone(),
-            }.update_control();
+            });
         }
     }
 
     match destination_system{
-        System::Unused => panic!("Destination system is unused"),
+        System::Unused => return Err(RuleViolation::UnusedSystem { system_id: destination_system_id }),
         System::Used {
             system_id,
             system_type,
@@ -42,50 +93,47 @@ pub fn move_ships(game_state: &GameState, origin_system_id: u8, destination_syst
             connects_to
         } => {
             let updated_ships = place_ships(&ships, &game_state.current_player, fresh, damaged);
-            game_state.systems[destination_system_id as usize] = System::Used {
+            game_state.set_system(destination_system_id, System::Used {
                 system_id: system_id, 
                 system_type: system_type.clone(), 
                 building_slots: building_slots.clone(), 
                 ships: updated_ships, 
                 controlled_by: controlled_by.clone(), 
-                connects_to: connects_to.clone() }.update_control()
+                connects_to: connects_to.clone() })
         }
     }
 
-    return game_state;
+    game_state.events.push(GameEvent::ShipsMoved { player: game_state.current_player.clone(), origin: origin_system_id, destination: destination_system_id, fresh: fresh, damaged: damaged });
+    return Ok(game_state);
 }
 
-fn bfs_search_systems(systems: &Vec<System>, start_system_id: u8, color: &Color) -> Vec<u8> {
-    if match &systems[(start_system_id.clone()/3) as usize] {
-        System::Used { system_type: SystemType::Gate, controlled_by, .. } => controlled_by == &None || controlled_by == &Some(color.clone()),
-        _ => false
-    } {return vec![start_system_id/8 as u8];}
-    let mut stack = vec![start_system_id/3 as u8];
-    let mut reachable: Vec<u8> = vec![];
-    while stack.len() != 0 {
-        let current = stack.pop().unwrap();
-        match &systems[current as usize] {
-            System::Unused => panic!("Cannot expand from unsused system"),
-            System::Used { connects_to, .. } => connects_to.iter()
-                .filter_map(|&i| match &systems[i as usize] {
-                    System::Unused => None,
-                    System::Used { system_type, controlled_by, .. } => {
-                        Some((i, system_type, controlled_by))
-                    },
-                }),
-        }.for_each(|(i, st, c)| {
-            reachable.push(i);
-            if st == &SystemType::Gate && (c == &None || c == &Some(color.clone())) {stack.push(i);}
-        });
+pub(crate) fn passable_gates(game_state: &GameState, color: &Color) -> impl Fn(u8) -> bool {
+    let systems = game_state.systems.clone();
+    let color = color.clone();
+    move |gate| match &systems[gate as usize] {
+        System::Used { controlled_by, .. } => controlled_by == &None || controlled_by == &Some(color.clone()),
+        System::Unused => false
     }
-    return reachable;
 }
 
-pub(crate) fn catapult(game_state: &GameState, origin_system: u8, destination_systems: Vec<(u8,u8,u8)>) -> GameState {
+// Catapulting ships leave through the gate of the origin and may only continue their way through gates,
+// which are not controlled by a rival. They can end up in any number of gates and at most one planet.
+pub(crate) fn catapult_destinations(game_state: &GameState, origin_system: u8, color: &Color) -> Vec<u8> {
+    let origin_gate = Reach::gate_of(origin_system);
+    let passable = passable_gates(game_state, color);
+    let reachable: Vec<u8> = if passable(origin_gate) {
+        game_state.reach.reachable_through_gates(origin_gate, passable)
+    } else {
+        vec![]
+    };
+    vec![origin_gate].into_iter().chain(reachable).filter(|s| *s != origin_system).unique().sorted().collect()
+}
+
+pub(crate) fn catapult(game_state: &GameState, origin_system: u8, destination_systems: Vec<(u8,u8,u8)>) -> Result<GameState, RuleViolation> {
     let current_player = game_state.current_player.clone();
 
-    let (building_slots, ships) = match &game_state.systems[origin_system as usize] {
-        System::Unused => panic!("Cannot catapult from Unused System"),
+    let (building_slots, ships) = match game_state.get_system(origin_system)? {
+        System::Unused => return Err(RuleViolation::UnusedSystem { system_id: origin_system }),
         System::Used {building_slots, ships, ..} => (building_slots,ships.get(&current_player).unwrap())
     };
 
@@ -93,32 +141,27 @@ pub(crate) fn catapult(game_state: &GameState, origin_system: u8, destination_sy
             BuildingSlot::Occupied { player, building_type: BuildingType::Starport, .. } if player == &current_player => true,
             _ => false
         });
-    if !has_loyal_starport {panic!("Cannot catapult from {:?}, because the system has no loyal Starport", origin_system)}
+    if !has_loyal_starport {return Err(RuleViolation::NoLoyalStarport { system_id: origin_system })}
+
+    if destination_systems.is_empty() {return Err(RuleViolation::NoDestination)}
+    if destination_systems.iter().any(|(_, fresh, damaged)| *fresh == 0 && *damaged == 0) {return Err(RuleViolation::NoShipsMoved)}
     
     //Check if we move less or equal to the ships present
     let (fresh, damaged) = destination_systems
         .iter()
-        .fold((0, 0), |(fresh_sum, damaged_sum), &(_, fresh, damaged)| {
-            (fresh_sum + fresh, damaged_sum + damaged)
+        .fold((0u8, 0u8), |(fresh_sum, damaged_sum), &(_, fresh, damaged)| {
+            (fresh_sum.saturating_add(fresh), damaged_sum.saturating_add(damaged))
         });
 
-    if fresh > ships.fresh || damaged > ships.damaged {panic!("Catapulting {:?} fresh and {:?} damaged Ships when only {:?} fresh and {:?} damaged are present", fresh, damaged, ships.fresh, ships.damaged)}
-
-    
-    let systems: Vec<System>  = destination_systems.iter()
-        .map(|(sys, _, _)| game_state.systems[*sys as usize].clone())
-        .collect();
+    if fresh > ships.fresh || damaged > ships.damaged {return Err(RuleViolation::NotEnoughShips { system_id: origin_system, fresh: fresh, damaged: damaged, fresh_present: ships.fresh, damaged_present: ships.damaged })}
 
     //Check only one destination is a Planet System
-    if systems.iter().filter(|s| match s {
-        System::Used { system_type: SystemType::Planet { .. }, .. } => true,
-        _ => false
-    }).count() > 1 {panic!("Cannot catapult into more than one Planet System")}
+    if destination_systems.iter().map(|(s, _, _)| s).unique().filter(|s| !Reach::is_gate(**s)).count() > 1 {return Err(RuleViolation::MoreThanOnePlanet { destinations: destination_systems.iter().map(|(s, _, _)| *s).collect() })}
     
-    let reachable = bfs_search_systems(&game_state.systems, origin_system, &current_player);
-    //Check all can be reached without entering a planet system or rival controlled System
-    if destination_systems.iter().any(|(s, _, _)| !reachable.contains(s)) {panic!("Cannot catapult into unreachable (because of lacking control) System")}
+    //Check all can be reached without passing a rival controlled gate or a cluster out of play
+    let reachable = catapult_destinations(game_state, origin_system, &current_player);
+    if let Some((s, _, _)) = destination_systems.iter().find(|(s, _, _)| !reachable.contains(s)) {return Err(RuleViolation::Unreachable { origin: origin_system, destination: *s })}
 
     return destination_systems.iter()
-        .fold(game_state.clone(), |g, (sys, fresh, damaged)| move_ships(&g, origin_system, *sys, *fresh, *damaged))
+        .try_fold(game_state.clone(), |g, (sys, fresh, damaged)| relocate_ships(&g, origin_system, *sys, *fresh, *damaged))
 }
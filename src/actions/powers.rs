@@ -0,0 +1,55 @@
+use crate::board::place_ships;
+use crate::data::game_state::{Action, BasicAction, BuildType, Color, GameState, ReserveType, ResourceType};
+use crate::data::leaders::Power;
+use crate::data::system::System;
+use crate::data::violations::RuleViolation;
+
+// Resource a prelude action needs, if only one type can pay for it regardless of the lead card
+fn needed_resource(action: &BasicAction) -> Option<ResourceType> {
+    match action {
+        BasicAction::Build { .. } | BasicAction::Repair { .. } => Some(ResourceType::Material),
+        BasicAction::Move { .. } | BasicAction::MoveFleets { .. } | BasicAction::Catapult { .. } => Some(ResourceType::Fuel),
+        BasicAction::Secure { .. } => Some(ResourceType::Relics),
+        _ => None
+    }
+}
+
+// Spent resources count as another type, if a power of the player allows it and the action needs it
+pub(crate) fn prelude_resource(game_state: &GameState, player: &Color, action: &BasicAction, resource: Option<ResourceType>) -> Option<ResourceType> {
+    let needed = needed_resource(action);
+    game_state.get_player_area(player).powers().iter().fold(resource, |r, p| match p {
+        Power::SubstituteResource { used, counts_as } if r.as_ref() == Some(used) && needed.as_ref() == Some(counts_as) => Some(counts_as.clone()),
+        _ => r
+    })
+}
+
+// Triggers the powers of the acting player after their action was executed
+pub(crate) fn after_action(game_state: &GameState, player: &Color, action: &Action) -> Result<GameState, RuleViolation> {
+    let basic_action = match action {
+        Action::MainAction { basic_action } | Action::PreludeResourceAction { basic_action, .. } => basic_action,
+        _ => return Ok(game_state.clone())
+    };
+    game_state.get_player_area(player).powers().iter().try_fold(game_state.clone(), |g, p| match (p, basic_action) {
+        (Power::ShipWithCity, BasicAction::Build { target_system, build_type: BuildType::City }) => place_ship_with_city(&g, player, *target_system),
+        _ => Ok(g)
+    })
+}
+
+fn place_ship_with_city(game_state: &GameState, player: &Color, target_system: u8) -> Result<GameState, RuleViolation> {
+    if game_state.get_player_area(player).reserve.get(&ReserveType::Ships) == Some(&0) {return Ok(game_state.clone())}
+
+    let mut new_game_state = game_state.clone();
+    match game_state.get_system(target_system)?.clone() {
+        System::Unused => return Err(RuleViolation::UnusedSystem { system_id: target_system }),
+        System::Used { system_id, system_type, building_slots, ships, controlled_by, connects_to } => new_game_state.set_system(target_system, System::Used {
+            system_id: system_id,
+            system_type: system_type,
+            building_slots: building_slots,
+            ships: place_ships(&ships, player, 1, 0),
+            controlled_by: controlled_by,
+            connects_to: connects_to
+        })
+    }
+    new_game_state.update_players_reserve(player, &ReserveType::Ships, -1)?;
+    Ok(new_game_state)
+}
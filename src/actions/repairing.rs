@@ -1,14 +1,15 @@
 use crate::data::game_state::{GameState, BuildType};
 use crate::data::system::{BuildingSlot, System, BuildingType};
+use crate::data::violations::RuleViolation;
 
 use crate::board::{remove_ships,place_ships};
 
-pub(crate) fn repair(game_state: &GameState, target_system: u8, build_type: BuildType) -> GameState {
+pub(crate) fn repair(game_state: &GameState, target_system: u8, build_type: BuildType) -> Result<GameState, RuleViolation> {
     let mut game_state = game_state.clone();
-    let system = game_state.systems[target_system as usize].clone();
+    let system = game_state.get_system(target_system)?.clone();
     let current_player = game_state.current_player.clone();
     match system {
-        System::Unused => panic!("Cannot repair in unused System"),
+        System::Unused => return Err(RuleViolation::UnusedSystem { system_id: target_system }),
         System::Used {
             system_id,
             system_type,
@@ -16,34 +17,38 @@ pub(crate) fn repair(game_state: &GameState, target_system: u8, build_type: Buil
             ships,
             controlled_by,
             connects_to
-        } => match build_type {
-            BuildType::Ship => {
-                let ships = remove_ships(&ships, &current_player, 0, 1);
-                let ships = place_ships(&ships, &current_player, 1, 0);
-                game_state.systems[target_system as usize] = System::Used {
-                    system_id: system_id,
-                    system_type: system_type,
-                    building_slots: building_slots,
-                    ships: ships,
-                    controlled_by: controlled_by,
-                    connects_to: connects_to
-                }.update_control();
-            },
-            BuildType::City => {
-                match (building_slots[0].clone(), building_slots[1].clone()) {
-                    (BuildingSlot::Occupied { fresh: false, player: current_player, building_type: BuildingType::City, used }, _) => building_slots[0] = BuildingSlot::Occupied { fresh: true, player: current_player, building_type: BuildingType::City, used },
-                    (_, BuildingSlot::Occupied { fresh: false, player: current_player, building_type: BuildingType::City, used }) => building_slots[1] = BuildingSlot::Occupied { fresh: true, player: current_player, building_type: BuildingType::City, used },
-                    _ => panic!("Cannot repair, because no valid repair target exists")
+        } => {
+            let ships = match build_type {
+                BuildType::Ship => {
+                    if ships.get(&current_player).unwrap().damaged == 0 {return Err(RuleViolation::NothingToRepair { system_id: target_system, build_type: build_type })}
+                    let ships = remove_ships(&ships, &current_player, 0, 1);
+                    place_ships(&ships, &current_player, 1, 0)
+                },
+                BuildType::City | BuildType::Starport => {
+                    let repaired_type = if build_type == BuildType::City {BuildingType::City} else {BuildingType::Starport};
+                    let damaged = building_slots.iter().position(|b| match b {
+                        BuildingSlot::Occupied { fresh: false, player, building_type, .. } => *player == current_player && *building_type == repaired_type,
+                        _ => false
+                    });
+                    match damaged {
+                        Some(i) => building_slots[i] = match &building_slots[i] {
+                            BuildingSlot::Occupied { player, building_type, used, .. } => BuildingSlot::Occupied { fresh: true, player: player.clone(), building_type: building_type.clone(), used: *used },
+                            BuildingSlot::Empty => BuildingSlot::Empty
+                        },
+                        None => return Err(RuleViolation::NothingToRepair { system_id: target_system, build_type: build_type })
+                    }
+                    ships
                 }
-            },
-            BuildType::Starport => {
-                match (building_slots[0].clone(), building_slots[1].clone()) {
-                    (BuildingSlot::Occupied { fresh: false, player: current_player, building_type: BuildingType::Starport, used }, _) => building_slots[0] = BuildingSlot::Occupied { fresh: true, player: current_player, building_type: BuildingType::Starport, used },
-                    (_, BuildingSlot::Occupied { fresh: false, player: current_player, building_type: BuildingType::Starport, used }) => building_slots[1] = BuildingSlot::Occupied { fresh: true, player: current_player, building_type: BuildingType::Starport, used },
-                    _ => panic!("Cannot repair, because no valid repair target exists")
-                }
-            }
+            };
+            game_state.set_system(target_system, System::Used {
+                system_id: system_id,
+                system_type: system_type,
+                building_slots: building_slots,
+                ships: ships,
+                controlled_by: controlled_by,
+                connects_to: connects_to
+            });
         }
     }
-    return game_state;
+    return Ok(game_state);
 }
@@ -2,27 +2,28 @@ use std::collections::HashMap;
 
 use crate::data::game_state::{GameState,Trophy, ReserveType, Color, PlayerArea};
 use crate::data::court_cards::{CourtCard, Guild, VoxPayload};
+use crate::data::violations::RuleViolation;
 
-pub(crate) fn secure(game_state: &GameState, target_card: u8, vox_payload: Option<VoxPayload>) -> GameState {
+pub(crate) fn secure(game_state: &GameState, target_card: u8, vox_payload: Option<VoxPayload>) -> Result<GameState, RuleViolation> {
     let current_player = game_state.current_player.clone();
-    let card = game_state.court[target_card as usize].clone();
+    let card = game_state.court.get(target_card as usize).cloned().ok_or(RuleViolation::UnknownCourtCard { card_id: target_card })?;
     let new_game_state = game_state.clone();
     
     if card.controlled_by() == Some(current_player.clone()) {
         match card {
             CourtCard::VoxCard { vox, agents } => {
-                let tropies: Vec<Trophy> = agents.iter()
+                let trophies: Vec<Trophy> = agents.iter()
                     .filter(|a| a.color != current_player)
                     .map(|a| Trophy{trophy_type: ReserveType::Agents, count: a.count, player: a.color.clone()})
                     .collect();
                 let players_agents = agents.iter().find(|a| a.color == current_player).unwrap().count;
                 let current_player_area = new_game_state.get_player_area(&current_player);
-                let combined_trophies = current_player_area.add_trophies(tropies);
+                let combined_trophies = current_player_area.add_trophies(trophies);
 
                 let new_players: HashMap<Color, PlayerArea> = new_game_state.players.iter().map(|(c,p)| if *c==current_player 
                                     {
                                         (c.clone(), PlayerArea{ 
-                                            tropies: combined_trophies.clone(),
+                                            trophies: combined_trophies.clone(),
                                             ..p.clone()
                                             })
                                     }
@@ -30,22 +31,25 @@ pub(crate) fn secure(game_state: &GameState, target_card: u8, vox_payload: Optio
                                 ).collect();
 
                 let mut new_game_state = GameState { players: new_players, ..new_game_state.clone() };
-                new_game_state.update_players_reserve(&current_player, &ReserveType::Agents, players_agents as i8);
+                new_game_state.update_players_reserve(&current_player, &ReserveType::Agents, players_agents as i8)?;
 
-                return (vox.on_secure)(&new_game_state, vox_payload.expect("VoxPayload required"));
+                let new_game_state = new_game_state.discard_court_card(target_card);
+
+                let vox_payload = vox_payload.ok_or(RuleViolation::MissingVoxPayload { card_id: target_card })?;
+                return Ok(vox.on_secure.resolve(&new_game_state, vox_payload)?.redraw_court_cards());
             },
             CourtCard::GuildCard { guild, agents } => {
-                let tropies: Vec<Trophy> = agents.iter()
+                let trophies: Vec<Trophy> = agents.iter()
                     .filter(|a| a.color != current_player)
                     .map(|a| Trophy{trophy_type: ReserveType::Agents, count: a.count, player: a.color.clone()})
                     .collect();
                 let players_agents = agents.iter().find(|a| a.color == current_player).unwrap().count;
                 let current_player_area = new_game_state.get_player_area(&current_player);
-                let combined_trophies = current_player_area.add_trophies(tropies);
+                let combined_trophies = current_player_area.add_trophies(trophies);
                 let new_guild_cards: Vec<Guild> = current_player_area.guild_cards.iter().cloned().chain(vec![guild.clone()]).collect();
                 let new_players: HashMap<Color, PlayerArea> = new_game_state.players.iter().map(|(c,p)| if *c==current_player 
                                     { (c.clone(), PlayerArea{ 
-                                        tropies: combined_trophies.clone(),
+                                        trophies: combined_trophies.clone(),
                                         guild_cards: new_guild_cards.clone(),
                                         ..p.clone()
                                     }
@@ -53,11 +57,12 @@ pub(crate) fn secure(game_state: &GameState, target_card: u8, vox_payload: Optio
                                     else {(c.clone(),p.clone())}
                                 ).collect();
                 let mut new_game_state = GameState { players: new_players, ..new_game_state.clone() };
-                new_game_state.update_players_reserve(&current_player, &ReserveType::Agents, players_agents as i8);
+                new_game_state.update_players_reserve(&current_player, &ReserveType::Agents, players_agents as i8)?;
+                new_game_state.court.remove(target_card as usize);
 
-                return new_game_state;
+                return Ok(new_game_state.redraw_court_cards());
             },
         }
     }
-    else { panic!("Can only secure controlled Card") };
+    else { Err(RuleViolation::CardNotControlled { card_id: target_card, player: current_player }) }
 }
@@ -1,18 +1,20 @@
 use crate::data::game_state::{GameState, Color, Trophy, ReserveType, TurnState};
 use crate::data::system::{BuildingType, System, SystemType};
+use crate::data::violations::RuleViolation;
 
-pub(crate) fn tax(game_state: &GameState, target_system: u8, target_player: Color) -> GameState {
-    let system = &game_state.systems[target_system as usize];
+pub(crate) fn tax(game_state: &GameState, target_system: u8, target_player: Color) -> Result<GameState, RuleViolation> {
+    game_state.check_seated(&target_player)?;
+    let system = game_state.get_system(target_system)?;
     let tax_rival = target_player != game_state.current_player;
 
     let mut new_game_state =  game_state.clone();
 
     match system {
-        System::Unused => panic!("Cannot tax Unused System"),
+        System::Unused => return Err(RuleViolation::UnusedSystem { system_id: target_system }),
         System::Used {system_type, controlled_by, .. } => {
             if tax_rival {
                 if controlled_by != &Some(game_state.current_player.clone()) {
-                    panic!("Cannot tax a rival in a System controlled by another player");
+                    return Err(RuleViolation::NotControlled { system_id: target_system, player: game_state.current_player.clone() });
                 }
                 let rivals_play_area = new_game_state.get_player_area(&target_player);
                 let current_player_area = new_game_state.get_player_area(&game_state.current_player);
@@ -22,12 +24,13 @@ pub(crate) fn tax(game_state: &GameState, target_system: u8, target_player: Colo
                     player: target_player.clone(),
                 }]);
                 new_game_state.players.insert(target_player.clone(), rivals_play_area);
-                new_game_state.update_players_reserve(&target_player, &ReserveType::Agents, -1);
+                new_game_state.update_players_reserve(&target_player, &ReserveType::Agents, -1)?;
             }
-            new_game_state.systems[target_system as usize] = new_game_state.systems[target_system as usize].use_building(&BuildingType::City, &target_player);
+            let taxed_system = new_game_state.systems[target_system as usize].use_building(&BuildingType::City, &target_player)?;
+            new_game_state.set_system(target_system, taxed_system);
             
             let taxed_resource = match system_type {
-                SystemType::Gate => panic!("Cannot tax Gate System"),
+                SystemType::Gate => return Err(RuleViolation::CannotTaxGate { system_id: target_system }),
                 SystemType::Planet { resource } => resource.clone(),
             };
 
@@ -40,5 +43,5 @@ pub(crate) fn tax(game_state: &GameState, target_system: u8, target_player: Colo
         }
 
     }
-    new_game_state
+    Ok(new_game_state)
 }
@@ -1,86 +1,99 @@
-use crate::data::game_state::{ActionCard, Ambition, AmbitionTypes, GameState, TurnState};
+use crate::data::events::{CardPlay, GameEvent};
+use crate::data::game_state::{ActionCard, Ambition, AmbitionTypes, GameState, PlayerArea, TurnState};
+use crate::data::violations::RuleViolation;
 
-pub(crate) fn play_lead_card(game_state: &GameState, card: ActionCard, declare: Option<AmbitionTypes>) -> GameState {
+pub(crate) fn play_lead_card(game_state: &GameState, card: ActionCard, declare: Option<AmbitionTypes>) -> Result<GameState, RuleViolation> {
+    if let Some((lead, _, _)) = &game_state.lead_card {return Err(RuleViolation::LeadCardPlayed { lead: lead.clone() })}
     let mut new_game_state = game_state.clone();
     let mut player_area = new_game_state.get_player_area(&new_game_state.current_player);
-    player_area.remove_action_card(card.clone());
+    player_area.remove_action_card(card.clone())?;
     new_game_state.players.insert(new_game_state.current_player.clone(), player_area);
     new_game_state.lead_card = Some((card.clone(), true, game_state.current_player.clone()));
+    new_game_state.events.push(GameEvent::CardPlayed { player: game_state.current_player.clone(), card: card.clone(), play: CardPlay::Lead, seize: false });
     new_game_state.turn_state = TurnState::Prelude { action_type: card.action_type.clone(), pips_left: card.pips };
     match declare {
         Some(ambition) => {
-            match &card.declared_ambition {
-                Some(possible_declare) => if possible_declare == &ambition 
-                        {declare_ambition(game_state, ambition)}
-                    else
-                        {panic!("AmbitionType on card {:?} does not match Ambition {:?}", &card, ambition)},
-                None => panic!("Cannot declare Ambition with {:?}", card),
-            }
+            if card.declared_ambition.as_ref() != Some(&ambition) {return Err(RuleViolation::CannotDeclareAmbition { card: card, ambition: ambition })}
+            declare_ambition(&new_game_state, ambition)
         },
-        None => new_game_state 
+        None => Ok(new_game_state)
     }
 }
 
-pub(crate) fn surpass(game_state: &GameState, card: ActionCard, seize: Option<ActionCard>) -> GameState {
-    let (lead_card, _, _) = game_state.lead_card.as_ref().expect("Cannot Surpass, if no lead card exists");
-    if card.action_type != lead_card.action_type {panic!("Cannot surpass with other card type")}
-    if card.number < lead_card.number {panic!("Cannot surpass with a lower card")};
+pub(crate) fn surpass(game_state: &GameState, card: ActionCard, seize: Option<ActionCard>) -> Result<GameState, RuleViolation> {
+    let (lead_card, _, _) = game_state.lead_card.as_ref().ok_or(RuleViolation::NoLeadCard)?;
+    if card.action_type != lead_card.action_type || card.number < lead_card.number {return Err(RuleViolation::CannotSurpass { card: card, lead: lead_card.clone() })}
     let mut new_game_state = game_state.clone();
     let mut player_area = new_game_state.get_player_area(&new_game_state.current_player);
-    player_area.remove_action_card(card.clone());
+    player_area.remove_action_card(card.clone())?;
+    check_seize(&player_area, &seize)?;
     new_game_state.players.insert(new_game_state.current_player.clone(), player_area);
     new_game_state.follow_cards.push((card.clone(), true, game_state.current_player.clone()));
+    new_game_state.events.push(GameEvent::CardPlayed { player: game_state.current_player.clone(), card: card.clone(), play: CardPlay::Surpass, seize: seize.is_some() });
     match seize {
-        Some(_) => todo!(),
+        Some(_) => new_game_state.seized = Some(new_game_state.current_player.clone()),
         None => {},
     }
     new_game_state.turn_state = TurnState::Prelude { action_type: card.action_type, pips_left: card.pips };
-    new_game_state
+    Ok(new_game_state)
 }
 
-pub(crate) fn copy(game_state: &GameState, card: ActionCard, seize: Option<ActionCard>) -> GameState {
-    let (lead_card, _, _) = game_state.lead_card.as_ref().unwrap();
+pub(crate) fn copy(game_state: &GameState, card: ActionCard, seize: Option<ActionCard>) -> Result<GameState, RuleViolation> {
+    let (lead_card, _, _) = game_state.lead_card.as_ref().ok_or(RuleViolation::NoLeadCard)?;
     let mut new_game_state = game_state.clone();
     let mut player_area = new_game_state.get_player_area(&new_game_state.current_player);
-    player_area.remove_action_card(card.clone());
+    player_area.remove_action_card(card.clone())?;
+    check_seize(&player_area, &seize)?;
     new_game_state.players.insert(new_game_state.current_player.clone(), player_area);
     new_game_state.follow_cards.push((card.clone(), false, game_state.current_player.clone()));
+    new_game_state.events.push(GameEvent::CardPlayed { player: game_state.current_player.clone(), card: card.clone(), play: CardPlay::Copy, seize: seize.is_some() });
     match seize {
         Some(_) => new_game_state.seized = Some(new_game_state.current_player.clone()),
         None => {},
     }
     new_game_state.turn_state = TurnState::Prelude { action_type: lead_card.action_type.clone(), pips_left: 1 };
-    new_game_state
+    Ok(new_game_state)
 }
 
-pub(crate) fn pivot(game_state: &GameState, card: ActionCard, seize: Option<ActionCard>) -> GameState {
-    let (lead_card, _, _) = game_state.lead_card.as_ref().unwrap();
-    if card.action_type == lead_card.action_type {panic!("Cannot Pivot with same card type")};
+pub(crate) fn pivot(game_state: &GameState, card: ActionCard, seize: Option<ActionCard>) -> Result<GameState, RuleViolation> {
+    let (lead_card, _, _) = game_state.lead_card.as_ref().ok_or(RuleViolation::NoLeadCard)?;
+    if card.action_type == lead_card.action_type {return Err(RuleViolation::CannotPivot { card: card, lead: lead_card.clone() })};
     let mut new_game_state = game_state.clone();
     let mut player_area = new_game_state.get_player_area(&new_game_state.current_player);
-    player_area.remove_action_card(card.clone());
+    player_area.remove_action_card(card.clone())?;
+    check_seize(&player_area, &seize)?;
     new_game_state.players.insert(new_game_state.current_player.clone(), player_area);
     new_game_state.follow_cards.push((card.clone(), true, game_state.current_player.clone()));
+    new_game_state.events.push(GameEvent::CardPlayed { player: game_state.current_player.clone(), card: card.clone(), play: CardPlay::Pivot, seize: seize.is_some() });
     match seize {
         Some(_) => new_game_state.seized = Some(new_game_state.current_player.clone()),
         None => {},
     }
     new_game_state.turn_state = TurnState::Prelude { action_type: card.action_type, pips_left: 1 };
-    new_game_state
+    Ok(new_game_state)
 }
 
-fn declare_ambition(game_state: &GameState, ambition: AmbitionTypes) -> GameState {
+// The card seizing the initiative has to be another card in the hand
+fn check_seize(player_area: &PlayerArea, seize: &Option<ActionCard>) -> Result<(), RuleViolation> {
+    match seize {
+        Some(card) if !player_area.action_cards.contains(card) => Err(RuleViolation::CardNotInHand { player: player_area.player.clone(), card: card.clone() }),
+        _ => Ok(())
+    }
+}
+
+fn declare_ambition(game_state: &GameState, ambition: AmbitionTypes) -> Result<GameState, RuleViolation> {
     let highest_ambition = game_state.ambition_markers.iter().max_by_key(|am| if am.flipped {am.first_place_flipped} else {am.first_place});
     let highest_ambition = match highest_ambition {
         Some(a) => a,
-        None => panic!("Cannot declare, because no AmbitionMarker is available")
+        None => return Err(RuleViolation::NoAmbitionMarker)
     };
     let mut ambitions = game_state.ambitions.clone();
     let ambition_box = ambitions.get(&ambition).unwrap();
-    ambitions.insert(ambition, Ambition{markers: ambition_box.markers.iter().chain(vec![highest_ambition]).cloned().collect(), .. ambition_box.clone()});
-    GameState{
+    ambitions.insert(ambition.clone(), Ambition{markers: ambition_box.markers.iter().chain(vec![highest_ambition]).cloned().collect(), .. ambition_box.clone()});
+    Ok(GameState{
         ambition_markers: game_state.ambition_markers.iter().filter(|am| am != &highest_ambition).cloned().collect(),
         ambitions: ambitions,
+        events: game_state.events.iter().cloned().chain(vec![GameEvent::AmbitionDeclared { player: game_state.current_player.clone(), ambition: ambition }]).collect(),
         .. game_state.clone()
-    }
-}
\ No newline at end of file
+    })
+}
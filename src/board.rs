@@ -2,58 +2,35 @@ use std::collections::{hash_map, HashMap};
 
 use itertools::{iproduct, Itertools};
 
+
 use crate::data::system::{System, SystemType, Ships, BuildingSlot, BuildingType};
-use crate::data::setup_cards::{SetupCard};
-use crate::data::game_state::{self, Ambition, AmbitionMarker, AmbitionTypes, Color, GameState, PlayerArea, ReserveType, ResourceSlot, ResourceType, TurnState};
+use crate::data::setup_cards::{SetupCard, SetupError, SetupOptions};
+use crate::data::leaders::{Leader, Lore, StartingPieces};
+use crate::data::reach::Reach;
+use crate::data::rng::GameRng;
+use crate::data::rules::{create_action_deck, RuleConfig};
+use crate::data::game_state::{self, Ambition, AmbitionTypes, Color, GameState, PlayerArea, ReserveType, ResourceSlot, ResourceType, TurnState};
 use crate::data::court_cards::{create_court_deck};
 
-pub fn get_cluster(system_id: u8) -> u8 {
-    match system_id {
-        0..6 => system_id,
-        6..24 => (system_id/3)-2 as u8,
-        _ => panic!()
-    }
-}
+pub(crate) fn create_reach(colors: &[Color], setup_card: &SetupCard) -> Vec<System> {
+    let empty_ships: HashMap<Color,Ships> = colors.iter().map(|x| (x.clone(), Ships{fresh: 0, damaged: 0})).collect();
 
-fn create_reach(setup_card: &SetupCard) -> Vec<System> {
-    let all_colors: Vec<Color> = vec![Color::Red, Color::Blue, Color::White, Color::Yellow];
-    let empty_ships: HashMap<Color,Ships> = all_colors[0..(setup_card.players as usize)].iter().map(|x| (x.clone(), Ships{fresh: 0, damaged: 0})).collect();
+    let reach = Reach::new(&setup_card.cluster_out_of_play);
 
     let mut systems:Vec<System> = vec![];
     // Create Gates
     for i in 0..6 {
-        let gate ={
-            let mut connections = vec![];
-            // Add Gate before
-            let mut j = 5;
-            while setup_card.cluster_out_of_play.contains(&((i+j)%6)){
-                j = j-1
-            }
-            connections.push((i+j)%6);
-
-            // Add Gate after
-            let mut j = 1;
-            while setup_card.cluster_out_of_play.contains(&((i+j)%6)){
-                j = j+1
-            }
-            connections.push((i+j)%6);
-
-            connections.push(6+3*i);
-            connections.push(7+3*i);
-            connections.push(8+3*i);
-
-            if setup_card.cluster_out_of_play.contains(&i) {
-                System::Unused
-            }else{
-                System::Used {
+        let gate = if reach.is_in_play(i) {
+            System::Used {
                 system_id: i,
                 system_type: SystemType::Gate,
                 building_slots: vec![],
                 ships: empty_ships.clone(),
                 controlled_by: None,
-                connects_to: connections
+                connects_to: reach.neighbours(i).clone()
             }
-        }
+        } else {
+            System::Unused
         };
         systems.push(gate);
     }
@@ -65,8 +42,9 @@ fn create_reach(setup_card: &SetupCard) -> Vec<System> {
 
     // Create Planets
     for i in 0..18{
+        let system_id = (6+i) as u8;
         let planet = {
-            if setup_card.cluster_out_of_play.contains(&(((i/3)as u8))){
+            if !reach.is_in_play(system_id){
                 System::Unused
             }else{
                 let systems_building_slots = {
@@ -77,24 +55,13 @@ fn create_reach(setup_card: &SetupCard) -> Vec<System> {
                     }
                 };
                 
-                // Gate connection
-                let mut connections = vec![(i/3) as u8];
-                
-                // Planets before
-                if (i == 5 || i == 14) && !setup_card.cluster_out_of_play.contains(&(((i+1)/3)as u8)) {connections.push((i+7) as u8)}
-                if i != 0 {if i/3 == (i-1)/3 {connections.push((i+5) as u8)}}
-                
-                // Planets after
-                if i/3 == (i+1)/3 {connections.push((i+7) as u8)}
-                if (i == 6 || i == 15) && !setup_card.cluster_out_of_play.contains(&(((i-1)/3)as u8)) {connections.push((i+5) as u8)}
-                
                 System::Used {
-                    system_id: (6+i) as u8,
+                    system_id: system_id,
                     system_type: SystemType::Planet {resource: resource_types[i].clone()},
                     building_slots: systems_building_slots,
                     ships: empty_ships.clone(),
                     controlled_by: None,
-                    connects_to: connections
+                    connects_to: reach.neighbours(system_id).clone()
                 }
         
             }
@@ -105,7 +72,7 @@ fn create_reach(setup_card: &SetupCard) -> Vec<System> {
     return systems;
 }
 
-fn setup_player_area(player_color: &Color, resources: (ResourceType,ResourceType)) -> PlayerArea {
+fn setup_player_area(player_color: &Color, resources: (ResourceType,ResourceType), leader: Option<Leader>, lore: Vec<Lore>) -> PlayerArea {
     return PlayerArea{
         player: player_color.clone(),
         power: 0,
@@ -115,18 +82,47 @@ fn setup_player_area(player_color: &Color, resources: (ResourceType,ResourceType
         reserve: vec![(ReserveType::Ships, 15), (ReserveType::Agents, 10), (ReserveType::Starports, 5), (ReserveType::Cities, 5)].into_iter().collect(),
         resource_slots: vec![ResourceSlot::Used { keys: 3, resource: resources.0 }, ResourceSlot::Used { keys: 1, resource: resources.1 }, ResourceSlot::Covered { keys: 1 }, ResourceSlot::Covered { keys: 2 }, ResourceSlot::Covered { keys: 1 }, ResourceSlot::Covered { keys: 3 }],
         captives: vec![],
-        tropies: vec![]
+        trophies: vec![],
+        leader: leader,
+        lore: lore
     }
 }
 
-pub fn setup_game(setup_card: &SetupCard) -> GameState {
+pub fn setup_game(setup_card: &SetupCard) -> Result<GameState, Vec<SetupError>> {
     let seed = rand::random::<u64>();
     setup_game_with_set_seed(setup_card, seed)
 }
 
-pub fn setup_game_with_set_seed(setup_card: &SetupCard, seed: u64) -> GameState {
-    let all_colors: Vec<Color> = vec![Color::Red, Color::Blue, Color::White, Color::Yellow].iter().take(setup_card.players.into()).cloned().collect();
-    let systems = create_reach(setup_card);
+pub fn setup_game_with_set_seed(setup_card: &SetupCard, seed: u64) -> Result<GameState, Vec<SetupError>> {
+    setup_game_with_options(setup_card, seed, &SetupOptions::default())
+}
+
+// Fails with every problem of the SetupCard, so they can be shown to the player creating the game
+pub fn setup_game_with_options(setup_card: &SetupCard, seed: u64, options: &SetupOptions) -> Result<GameState, Vec<SetupError>> {
+    setup_card.validate()?;
+    options.seating.validate(setup_card.players).map_err(|e| vec![e])?;
+    // Seats in turn order starting with the first player, who gets the first A, B and C locations
+    let (seats, rng) = options.seating.seats(setup_card.players, &GameRng::new(seed));
+    let first_player = options.first_player.clone().unwrap_or(seats[0].clone());
+    let first_seat = seats.iter().position(|c| *c == first_player).ok_or_else(|| vec![SetupError::FirstPlayerNotSeated { player: first_player.clone(), seats: seats.clone() }])?;
+    let all_colors: Vec<Color> = seats.iter().cycle().skip(first_seat).take(seats.len()).cloned().collect();
+
+    // Leaders replace the starting resources and pieces, every player keeps their leader and lore in their PlayerArea
+    if let Some(draft) = &options.leaders_and_lore {
+        if draft.players != all_colors || !draft.is_complete() {return Err(vec![SetupError::IncompleteDraft { players: all_colors }])}
+    }
+    if let Some(campaign) = &options.campaign {
+        if campaign.players.len() != all_colors.len() || !all_colors.iter().all(|c| campaign.players.contains_key(c)) {
+            return Err(vec![SetupError::CampaignPlayers { campaign: campaign.players.keys().cloned().sorted().collect(), players: all_colors }])
+        }
+        if campaign.is_finished() {return Err(vec![SetupError::FinishedCampaign { act: campaign.act, acts: campaign.acts }])}
+    }
+    let picks: HashMap<Color, (Leader, Lore)> = all_colors.iter()
+        .filter_map(|c| options.leaders_and_lore.as_ref().and_then(|d| d.picks_of(c)).map(|p| (c.clone(), p)))
+        .collect();
+    let starting_pieces = |c: &Color| picks.get(c).map(|(leader, _)| leader.starting_pieces.clone()).unwrap_or_default();
+
+    let systems = create_reach(&all_colors, setup_card);
     let mut players: Vec<PlayerArea> = all_colors[0..(setup_card.players as usize)]
         .iter()
         .zip(
@@ -134,12 +130,15 @@ pub fn setup_game_with_set_seed(setup_card: &SetupCard, seed: u64) -> GameState
             .map(|(a,b)| {
                 match (&systems[*a as usize], &systems[*b as usize]) {
                     (System::Used{system_type: SystemType::Planet { resource: r1 }, ..}, System::Used{system_type: SystemType::Planet { resource: r2 }, ..}) => (r1.clone(), r2.clone()),
-                    _ => panic!()
+                    _ => panic!("A and B locations {:?} and {:?} must be planets", a, b)
                 }
             })
         
         )
-        .map(|(c, r)| setup_player_area(c, r) ).collect();
+        .map(|(c, r)| match picks.get(c) {
+            Some((leader, lore)) => setup_player_area(c, leader.resources.clone(), Some(leader.clone()), vec![lore.clone()]),
+            None => setup_player_area(c, r, None, vec![])
+        }).collect();
     players[0].initiative = true;
 
     let systems = setup_card
@@ -161,8 +160,8 @@ pub fn setup_game_with_set_seed(setup_card: &SetupCard, seed: u64) -> GameState
                 } => *system = System::Used { 
                     system_id: *system_id,
                     system_type: system_type.clone(),
-                    building_slots: place_building(&building_slots, BuildingSlot::Occupied { fresh: true, player: color.clone(), building_type: BuildingType::City , used: false}),
-                    ships: place_ships(&ships, &color, 3, 0),
+                    building_slots: place_building(&building_slots, BuildingSlot::Occupied { fresh: true, player: color.clone(), building_type: starting_pieces(color).a_building, used: false}),
+                    ships: place_ships(&ships, &color, starting_pieces(color).a_ships, 0),
                     controlled_by: controlled_by.clone(),
                     connects_to: connects_to.to_vec()
                 },
@@ -190,8 +189,8 @@ pub fn setup_game_with_set_seed(setup_card: &SetupCard, seed: u64) -> GameState
                 } => *system = System::Used { 
                     system_id: *system_id,
                     system_type: system_type.clone(),
-                    building_slots: place_building(&building_slots, BuildingSlot::Occupied { fresh: true, player: color.clone(), building_type: BuildingType::Starport , used: false}),
-                    ships: place_ships(&ships, &color, 3, 0),
+                    building_slots: place_building(&building_slots, BuildingSlot::Occupied { fresh: true, player: color.clone(), building_type: starting_pieces(color).b_building, used: false}),
+                    ships: place_ships(&ships, &color, starting_pieces(color).b_ships, 0),
                     controlled_by: controlled_by.clone(),
                     connects_to: connects_to.to_vec()
                 },
@@ -220,7 +219,7 @@ pub fn setup_game_with_set_seed(setup_card: &SetupCard, seed: u64) -> GameState
                     system_id: *system_id,
                     system_type: system_type.clone(),
                     building_slots: building_slots.clone(),
-                    ships: place_ships(&ships, &color, 2, 0),
+                    ships: place_ships(&ships, &color, starting_pieces(color).c_ships, 0),
                     controlled_by: controlled_by.clone(),
                     connects_to: connects_to.to_vec()
                 },
@@ -236,29 +235,29 @@ pub fn setup_game_with_set_seed(setup_card: &SetupCard, seed: u64) -> GameState
         resource_reserve.insert(key.clone(), value);
     }
 
-    let ambition_markers = vec![
-        AmbitionMarker {first_place: 2, second_place: 0, flipped: false, first_place_flipped: 4, second_place_flipped: 2},
-        AmbitionMarker {first_place: 3, second_place: 2, flipped: false, first_place_flipped: 6, second_place_flipped: 4},
-        AmbitionMarker {first_place: 5, second_place: 3, flipped: false, first_place_flipped: 9, second_place_flipped: 5}
-    ];
+    let rules = options.rules.clone().unwrap_or_else(|| RuleConfig::for_players(all_colors.len()));
+    let (action_deck, rng) = rng.shuffle(&create_action_deck(&rules));
 
     let ambitions = vec![AmbitionTypes::Tycoon, AmbitionTypes::Tyrant, AmbitionTypes::Warlord, AmbitionTypes::Keeper, AmbitionTypes::Empath]
         .iter()
         .map(|a| (a.clone(), Ambition {ambition_type: a.clone(), markers: vec![], discarded_resources: vec![]}))
         .collect();
 
-    let court_draw_pile = create_court_deck(all_colors.clone(), seed);
+    let (court_draw_pile, rng) = create_court_deck(all_colors.clone(), &rng);
 
     let mut game_state = GameState{
         players: players.clone().into_iter().map(|p| (p.player.clone(), p)).collect(),
-        current_player: Color::Red,
+        current_player: first_player.clone(),
         players_in_round: players.len() as u8,
         turn_state: TurnState::TrickTaking,
         next_turn_states: vec![],
-        initiative: Color::Red,
+        initiative: first_player.clone(),
+        seating: all_colors.clone(),
         seized: None,
         zero_marker: false,
         chapter: 1,
+        rules: rules.clone(),
+        action_deck: action_deck,
         systems: systems,
         resource_reserve: resource_reserve,
         court: vec![],
@@ -267,23 +266,29 @@ pub fn setup_game_with_set_seed(setup_card: &SetupCard, seed: u64) -> GameState
         action_discard: vec![],
         lead_card: None,
         follow_cards: vec![],
-        ambition_markers: ambition_markers,
-        ambitions: ambitions  
+        ambition_markers: rules.ambition_markers.clone(),
+        ambitions: ambitions,
+        reach: Reach::new(&setup_card.cluster_out_of_play),
+        events: vec![],
+        rng: rng,
+        campaign: options.campaign.clone()
     }.redraw_court_cards();
 
-    let reserve_diff = iproduct!(vec![(ReserveType::Cities, -1),(ReserveType::Starports,-1),(ReserveType::Ships,-8)], all_colors.clone());
-
-    for ((r,d),c) in reserve_diff {
-        game_state.update_players_reserve(&c,& r,d);
+    for c in all_colors.iter() {
+        let StartingPieces { a_ships, a_building, b_ships, b_building, c_ships } = starting_pieces(c);
+        let buildings = |building_type: BuildingType| [&a_building, &b_building].iter().filter(|b| ***b == building_type).count() as i8;
+        game_state.update_players_reserve(c, &ReserveType::Cities, -buildings(BuildingType::City)).unwrap_or_else(|e| panic!("Cannot place starting pieces: {}", e));
+        game_state.update_players_reserve(c, &ReserveType::Starports, -buildings(BuildingType::Starport)).unwrap_or_else(|e| panic!("Cannot place starting pieces: {}", e));
+        game_state.update_players_reserve(c, &ReserveType::Ships, -((a_ships + b_ships + 2 * c_ships) as i8)).unwrap_or_else(|e| panic!("Cannot place starting pieces: {}", e));
     }
 
     for p in all_colors {
         let current = game_state.players.get(&p).unwrap();
-        let new = current.update_resource_slots().0;
+        let new = current.update_resource_slots(&rules).0;
         game_state.players.insert(p, new);
     }
 
-    return game_state;
+    return Ok(game_state);
 
 }
 
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::game_state::{Color, GameState, ResourceSlot, ResourceType};
+use super::rng::GameRng;
+use super::system::System;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Status {
+    // Leads the Empire and holds the Imperial Council
+    Regent,
+    Imperial,
+    Outlaw
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Objective {
+    HavePower {power: u8},
+    ControlSystems {systems: u8},
+    HoldResources {resource: ResourceType, count: u8},
+    HoldTrophies {trophies: u8}
+}
+
+impl Objective {
+    pub fn is_met(&self, game_state: &GameState, player: &Color) -> bool {
+        let player_area = game_state.get_player_area(player);
+        match self {
+            Objective::HavePower { power } => player_area.power >= *power,
+            Objective::ControlSystems { systems } => game_state.systems.iter().filter(|s| match s {
+                System::Used { controlled_by, .. } => controlled_by.as_ref() == Some(player),
+                System::Unused => false
+            }).count() >= *systems as usize,
+            Objective::HoldResources { resource, count } => {
+                let held = player_area.guild_cards.iter().filter(|g| g.resource == *resource).count()
+                    + player_area.resource_slots.iter().filter(|s| matches!(s, ResourceSlot::Used { resource: r, .. } if r == resource)).count();
+                held >= *count as usize
+            },
+            Objective::HoldTrophies { trophies } => player_area.get_trophies() >= *trophies
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fate {
+    pub title: String,
+    pub description: String,
+    pub objective: Objective
+}
+
+pub fn create_fates() -> Vec<Fate> {
+    vec![
+        Fate {
+            title: "Steward".to_string(),
+            description: "Control 5 systems at the end of a chapter.".to_string(),
+            objective: Objective::ControlSystems { systems: 5 }
+        },
+        Fate {
+            title: "Founder".to_string(),
+            description: "Hold 3 Material at the end of a chapter.".to_string(),
+            objective: Objective::HoldResources { resource: ResourceType::Material, count: 3 }
+        },
+        Fate {
+            title: "Magnate".to_string(),
+            description: "Hold 3 Fuel at the end of a chapter.".to_string(),
+            objective: Objective::HoldResources { resource: ResourceType::Fuel, count: 3 }
+        },
+        Fate {
+            title: "Advocate".to_string(),
+            description: "Have 10 power at the end of a chapter.".to_string(),
+            objective: Objective::HavePower { power: 10 }
+        },
+        Fate {
+            title: "Warden".to_string(),
+            description: "Hold 4 trophies at the end of a chapter.".to_string(),
+            objective: Objective::HoldTrophies { trophies: 4 }
+        },
+        Fate {
+            title: "Pathfinder".to_string(),
+            description: "Hold 2 Relics at the end of a chapter.".to_string(),
+            objective: Objective::HoldResources { resource: ResourceType::Relics, count: 2 }
+        }
+    ]
+}
+
+// Everything a player carries from one act into the next
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CampaignPlayer {
+    pub player: Color,
+    pub status: Status,
+    pub fate: Fate,
+    pub objective_met: bool,
+    // Acts in which the objective of the fate was met
+    pub objectives_met: u8,
+    pub power: u8
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Blight {
+    pub system_id: u8,
+    pub fresh: bool
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CampaignState {
+    pub act: u8,
+    pub acts: u8,
+    pub players: HashMap<Color, CampaignPlayer>,
+    pub blight: Vec<Blight>
+}
+
+impl CampaignState {
+    // The first player starts as Regent, every other player as Imperial, and every player draws a fate
+    pub fn new(players: Vec<Color>, acts: u8, rng: &GameRng) -> (CampaignState, GameRng) {
+        let (fates, rng) = rng.shuffle(&create_fates());
+        if players.len() > fates.len() {panic!("Cannot deal fates to {:?} players", players.len())}
+
+        (CampaignState {
+            act: 1,
+            acts: acts,
+            players: players.iter().zip(fates).enumerate().map(|(i, (c, fate))| (c.clone(), CampaignPlayer {
+                player: c.clone(),
+                status: if i == 0 {Status::Regent} else {Status::Imperial},
+                fate: fate,
+                objective_met: false,
+                objectives_met: 0,
+                power: 0
+            })).collect(),
+            blight: vec![]
+        }, rng)
+    }
+
+    pub fn get_player(&self, player: &Color) -> &CampaignPlayer {
+        self.players.get(player).expect("Cannot find CampaignPlayer")
+    }
+
+    pub fn regent(&self) -> Option<Color> {
+        self.players.values().find(|p| p.status == Status::Regent).map(|p| p.player.clone())
+    }
+
+    pub fn set_status(&self, player: &Color, status: Status) -> CampaignState {
+        if status == Status::Regent && self.regent().is_some_and(|r| r != *player) {panic!("Cannot make {:?} Regent, while {:?} is Regent", player, self.regent().unwrap())}
+        let mut new_campaign = self.clone();
+        new_campaign.players.get_mut(player).expect("Cannot find CampaignPlayer").status = status;
+        new_campaign
+    }
+
+    pub fn blight_in(&self, system_id: u8) -> Vec<Blight> {
+        self.blight.iter().filter(|b| b.system_id == system_id).cloned().collect()
+    }
+
+    pub fn place_blight(&self, system_id: u8) -> CampaignState {
+        let mut new_campaign = self.clone();
+        new_campaign.blight.push(Blight { system_id: system_id, fresh: true });
+        new_campaign
+    }
+
+    pub fn remove_blight(&self, system_id: u8) -> CampaignState {
+        let mut new_campaign = self.clone();
+        let i = new_campaign.blight.iter().position(|b| b.system_id == system_id).unwrap_or_else(|| panic!("No Blight in System {:?}", system_id));
+        new_campaign.blight.remove(i);
+        new_campaign
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.act > self.acts
+    }
+}
+
+// Objectives are checked at the end of every chapter, once met they stay met for the act
+pub fn check_objectives(game_state: &GameState) -> GameState {
+    let Some(campaign) = &game_state.campaign else {return game_state.clone()};
+    let mut new_campaign = campaign.clone();
+    for p in new_campaign.players.values_mut() {
+        p.objective_met = p.objective_met || p.fate.objective.is_met(game_state, &p.player);
+    }
+    GameState { campaign: Some(new_campaign), ..game_state.clone() }
+}
+
+// Closes the act of a running game and returns the campaign to start the next act with
+pub fn end_act(game_state: &GameState) -> CampaignState {
+    let game_state = check_objectives(game_state);
+    let mut campaign = game_state.campaign.clone().expect("Cannot end an act outside of a campaign");
+    if campaign.is_finished() {panic!("Cannot end act {:?} of a campaign with {:?} acts", campaign.act, campaign.acts)}
+
+    for p in campaign.players.values_mut() {
+        p.power += game_state.get_player_area(&p.player).power;
+        if p.objective_met {p.objectives_met += 1}
+        p.objective_met = false;
+    }
+    campaign.act += 1;
+    campaign
+}
@@ -1,13 +1,16 @@
-use super::game_state::{GameState, Color, Agents, ResourceType, AmbitionTypes, PreludeActionPayload};
+use serde::{Deserialize, Serialize};
+
+use super::game_state::{GameState, Color, Agents, ResourceType, AmbitionTypes};
 use super::system::System;
+use super::rng::GameRng;
+use super::violations::RuleViolation;
 use crate::data::game_state::ReserveType;
-use crate::board::{get_cluster, place_ships};
+use crate::board::place_ships;
+use crate::data::reach::Reach;
 
 use itertools::Itertools;
 
-use rand::prelude::*;
-
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CourtCard {
     VoxCard {vox: Vox, agents: Vec<Agents> },
     GuildCard {guild: Guild, agents: Vec<Agents>}
@@ -34,20 +37,39 @@ impl CourtCard {
             }
         }
     }
+
+    pub fn title(&self) -> String {
+        match self {
+            CourtCard::VoxCard { vox, .. } => vox.title.clone(),
+            CourtCard::GuildCard { guild, .. } => guild.title.clone(),
+        }
+    }
+
+    pub fn clear_agents(&self) -> CourtCard {
+        match self {
+            CourtCard::VoxCard { vox, agents } => CourtCard::VoxCard { vox: vox.clone(), agents: agents.iter().map(|a| Agents { color: a.color.clone(), count: 0 }).collect() },
+            CourtCard::GuildCard { guild, agents } => CourtCard::GuildCard { guild: guild.clone(), agents: agents.iter().map(|a| Agents { color: a.color.clone(), count: 0 }).collect() },
+        }
+    }
 }
 
-fn dummy_function_prelude(game_state: &GameState, payload: PreludeActionPayload) -> GameState {
-    panic!("This function should not be called, it is a placeholder for the PreludeAction");
+// Some court cards carry a player count icon and only enter play with at least that many players
+pub fn min_players(title: &str) -> u8 {
+    match title {
+        "Mining Interest" | "Shipping Interests" | "Prison Wardens" | "Secret Order" => 3,
+        "Admin Union" | "Spacing Union" | "Court Enforcers" | "Galactic Bards" => 4,
+        _ => 2
+    }
 }
 
-pub fn create_court_deck(players: Vec<Color>, seed:u64) -> Vec<CourtCard> {
+pub fn create_court_deck(players: Vec<Color>, rng: &GameRng) -> (Vec<CourtCard>, GameRng) {
     let agents: Vec<Agents> = players.iter().map(|color| Agents { color: color.clone(), count: 0 }).collect();
     let mut court = vec![
         CourtCard::VoxCard {
             vox: Vox {
                 title: "Mass Uprising".to_string(),
                 description: "When Secured: Choose a cluster on the map. You place 1 ship in each system of that cluster. Discard this card.".to_string(),
-                on_secure: mass_uprising
+                on_secure: VoxEffect::MassUprising
             },
             agents: agents.clone()
         },
@@ -62,7 +84,7 @@ pub fn create_court_deck(players: Vec<Color>, seed:u64) -> Vec<CourtCard> {
         },
         CourtCard::GuildCard { 
             guild: Guild { 
-                title: "Mining_Interest".to_string(), 
+                title: "Mining Interest".to_string(), 
                 description: "".to_string(), 
                 resource: ResourceType::Material, 
                 keys: 2,
@@ -215,7 +237,7 @@ pub fn create_court_deck(players: Vec<Color>, seed:u64) -> Vec<CourtCard> {
         },
         CourtCard::GuildCard { 
             guild: Guild { 
-                title: "Silver Tounges".to_string(), 
+                title: "Silver Tongues".to_string(), 
                 description: "".to_string(), 
                 resource: ResourceType::Psionics, 
                 keys: 2, 
@@ -277,30 +299,29 @@ pub fn create_court_deck(players: Vec<Color>, seed:u64) -> Vec<CourtCard> {
             agents: agents.clone() 
         }
     ];
-    let mut rng = StdRng::seed_from_u64(seed);
-    court.shuffle(&mut rng);
-    return court;
+    court.retain(|c| min_players(&c.title()) as usize <= players.len());
+    return rng.shuffle(&court);
 }
 
-fn mass_uprising(game_state: &GameState, vox_payload: VoxPayload) -> GameState {
+fn mass_uprising(game_state: &GameState, vox_payload: VoxPayload) -> Result<GameState, RuleViolation> {
     match vox_payload {
         VoxPayload::MassUprising { target_systems } => {
-            let reserve_ships = game_state.players.get(&game_state.current_player).unwrap().reserve.get(&ReserveType::Ships).unwrap();
-
-            if target_systems.len() != 4 && *reserve_ships >= 4 {
-                panic!("Invalid number of target systems for mass uprising: {:?}", target_systems);
-            } 
+            let reserve_ships = *game_state.players.get(&game_state.current_player).unwrap().reserve.get(&ReserveType::Ships).unwrap();
 
-            if target_systems.len() > *reserve_ships as usize {
-                panic!("Cannot place {:?} Ships using Mass Uprising, when only {:?} are in reserve", target_systems.len(), reserve_ships)
+            // As many ships as the reserve allows
+            if target_systems.len() != reserve_ships.min(4) as usize {
+                return Err(RuleViolation::WrongTargetCount { expected: reserve_ships.min(4), found: target_systems.len() as u8 });
             }
 
+            if let Some(s) = target_systems.iter().find(|s| !game_state.reach.is_in_play(**s)) {return Err(RuleViolation::UnusedSystem { system_id: *s })}
+
             //check if all target systems are in same cluster
-            if !target_systems.iter().map(|s| get_cluster(*s)).tuple_windows().all(|(a, b)| a == b) {
-                panic!("Systems are not all in the same cluster")
+            if !target_systems.iter().map(|s| Reach::cluster_of(*s)).tuple_windows().all(|(a, b)| a == b) {
+                return Err(RuleViolation::NotInOneCluster { systems: target_systems })
             }
 
-            let new_systems = game_state.systems.iter().map(|s| {
+            let mut new_game_state = game_state.clone();
+            new_game_state.update_systems(|s| {
                 match s {
                     System::Used { 
                         system_id,
@@ -315,41 +336,64 @@ fn mass_uprising(game_state: &GameState, vox_payload: VoxPayload) -> GameState {
                             ships: place_ships(ships, &game_state.current_player, 1, 0),
                             controlled_by: controlled_by.clone(),
                             connects_to: connects_to.clone() 
-                        }.update_control(),
+                        },
                     _ => s.clone()
                 }
             });
             
-            let mut new_game_state = GameState {
-                systems: new_systems.collect(),
-                ..game_state.clone()
-            };
-            
-            new_game_state.update_players_reserve(&game_state.current_player, &ReserveType::Ships, -(target_systems.len() as i8));
+            new_game_state.update_players_reserve(&game_state.current_player, &ReserveType::Ships, -(target_systems.len() as i8))?;
 
-            return new_game_state;
+            return Ok(new_game_state);
         }
-        _ => panic!("Cannot call mass_uprising with {:?}", vox_payload)
+        _ => Err(RuleViolation::WrongVoxPayload { payload: vox_payload })
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Vox {
     pub title: String,
     pub description: String,
-    pub on_secure: fn(&GameState, VoxPayload) -> GameState
+    pub on_secure: VoxEffect
+}
+
+// Effects of the Vox cards, named instead of function pointers so cards can be serialized
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VoxEffect {
+    MassUprising
+}
+
+impl VoxEffect {
+    pub fn resolve(&self, game_state: &GameState, vox_payload: VoxPayload) -> Result<GameState, RuleViolation> {
+        match self {
+            VoxEffect::MassUprising => mass_uprising(game_state, vox_payload)
+        }
+    }
 }
     
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Guild {
     pub title: String,
     pub description: String,
     pub resource: ResourceType,
     pub keys: u8,
-    pub prelude_action: Option<fn(&GameState, PreludeActionPayload) -> GameState>
+    pub prelude_action: Option<GuildPrelude>
+}
+
+// Prelude actions printed on Guild cards, none of them is implemented yet
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GuildPrelude {
+    Interest,
+    Steal,
+    Union,
+    PlaceShips,
+    Farseers,
+    RelicFence,
+    SilverTongues,
+    ElderBroker,
+    GateKeepers
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum VoxPayload {
     MassUprising {target_systems: Vec<u8>},
     PopulistDemands {ambition: Option<AmbitionTypes>},
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use super::game_state::{Action, ActionCard, AmbitionTypes, Color, Dice, ResourceType};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CardPlay {
+    Lead,
+    Surpass,
+    Copy,
+    Pivot
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DieFace {
+    pub self_hits: u8,
+    pub intercept: u8,
+    pub hits: u8,
+    pub building_hits: u8,
+    pub keys: u8
+}
+
+// Entries of the game history, the log in GameState is only ever appended to
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameEvent {
+    CourtDiscardReshuffled {cards: u8},
+    CourtNotRefilled {court_size: u8, missing: u8},
+    ActionTaken {player: Color, action: Action},
+    CardPlayed {player: Color, card: ActionCard, play: CardPlay, seize: bool},
+    AmbitionDeclared {player: Color, ambition: AmbitionTypes},
+    ShipsMoved {player: Color, origin: u8, destination: u8, fresh: u8, damaged: u8},
+    DiceRolled {player: Color, target_system: u8, target_player: Color, faces: Vec<(Dice, DieFace)>},
+    ResourceChanged {player: Color, resource: ResourceType, change: i8},
+    ControlChanged {system_id: u8, before: Option<Color>, after: Option<Color>},
+    AmbitionScored {ambition: AmbitionTypes, points: Vec<(Color, u8)>},
+    RoundEnded {initiative: Color},
+    ChapterEnded {chapter: u8},
+    GameEnded {winners: Vec<Color>}
+}
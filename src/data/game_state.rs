@@ -1,9 +1,20 @@
 use std::collections::HashMap;
 
-use super::court_cards::{CourtCard, VoxPayload, Guild};
-use super::system::{System};
+use itertools::Itertools;
+
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+use super::campaign::CampaignState;
+use super::court_cards::{CourtCard, VoxPayload, Guild};
+use super::events::GameEvent;
+use super::leaders::{Leader, Lore, Power};
+use super::reach::Reach;
+use super::rng::GameRng;
+use super::rules::RuleConfig;
+use super::system::{ControlReport, System};
+use super::violations::RuleViolation;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Color {
     Red,
     Blue,
@@ -11,7 +22,7 @@ pub enum Color {
     Yellow
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ResourceType {
     Fuel,
     Material,
@@ -19,7 +30,7 @@ pub enum ResourceType {
     Relics,
     Psionics
 }
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PreludeActionPayload {
     Interest {target_resource: ResourceType, steal_from: Vec<(Color,u8)>},
     Steal {target_resource: ResourceType, target_player: ResourceType},
@@ -27,46 +38,47 @@ pub enum PreludeActionPayload {
     PlaceShips {target_system: u8},
     Farseers {cards: Vec<ActionCard>},
     RelicFence {target_resource: ResourceType},
-    SilverTounges,
+    SilverTongues,
     ElderBroker,
     GateKeepers
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ResourceSlot {
     Used {keys: u8, resource: ResourceType},
     Unused {keys: u8},
     Covered {keys: u8}
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Agents{
     pub color: Color,
     pub count: u8
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Trophy {
     pub trophy_type: ReserveType,
     pub count: u8,
     pub player: Color
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BuildType{
     Starport,
     City,
     Ship
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+// Ordered as battles list their dice
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Dice{
     Skirmish,
     Assault,
     Raid
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HitTarget {
     Damaged,
     Fresh,
@@ -74,26 +86,28 @@ pub enum HitTarget {
     Building {building_id: u8}
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RaidTarget{
     Resource {resource_id: u8},
     GuildCard {card_id: u8}
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BasicAction{
     Build {target_system: u8, build_type: BuildType},
     Repair {target_system: u8, build_type: BuildType},
     Tax {target_system: u8, target_player: Color},
     Influence {card_id: u8},
     Move {origin_id: u8, destination_id: u8, fresh_ships: u8, damaged_ships: u8},
+    // Every fleet holds its path starting in its origin, and how many fresh and damaged ships follow it into the destination
+    MoveFleets {destination_id: u8, fleets: Vec<(Vec<u8>,u8,u8)>},
     Secure {card_id: u8, vox_payload: Option<VoxPayload>},
     Battle {target_system: u8, target_player: Color, dice: Vec<Dice>},
     // The destination systems hold Information about the systems, Ships end up, and how many fresh and damaged ships move there
     Catapult {origin_system: u8, destination_systems: Vec<(u8,u8,u8)>}
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Action{
     PlayLeadCard {card: ActionCard, declare: Option<AmbitionTypes>},
     Pass,
@@ -110,7 +124,7 @@ pub enum Action{
     EndTurn
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ActionType{
     Administration,
     Agression,
@@ -118,7 +132,7 @@ pub enum ActionType{
     Mobilization
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ActionCard{
     pub action_type: ActionType,
     pub number: u8,
@@ -126,7 +140,7 @@ pub struct ActionCard{
     pub declared_ambition: Option<AmbitionTypes>
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ReserveType {
     Ships,
     Agents,
@@ -134,7 +148,7 @@ pub enum ReserveType {
     Cities
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PlayerArea {
     pub player: Color,
     pub power: u8,
@@ -144,12 +158,14 @@ pub struct PlayerArea {
     pub reserve: HashMap<ReserveType,u8>,
     pub resource_slots: Vec<ResourceSlot>,
     pub captives: Vec<Agents>,
-    pub tropies: Vec<Trophy>
+    pub trophies: Vec<Trophy>,
+    pub leader: Option<Leader>,
+    pub lore: Vec<Lore>
 }
 
 impl PlayerArea {
-    pub fn add_trophies(&self, tropies: Vec<Trophy>) -> Vec<Trophy>{
-        let combined = tropies.iter().fold(self.tropies.clone(), {
+    pub fn add_trophies(&self, trophies: Vec<Trophy>) -> Vec<Trophy>{
+        let combined = trophies.iter().fold(self.trophies.clone(), {
             |mut acc, trophy| {
                 if let Some(existing) = acc.iter_mut().find(|t| t.trophy_type == trophy.trophy_type && t.player == trophy.player) {
                     existing.count += trophy.count;
@@ -166,20 +182,21 @@ impl PlayerArea {
         self.action_cards.extend(cards);
     }
 
-    pub fn remove_action_card(&mut self, card: ActionCard) {
+    pub fn remove_action_card(&mut self, card: ActionCard) -> Result<(), RuleViolation> {
         let ind = self.action_cards.iter().position(|c| *c == card);
         match ind {
-            Some(i) => { self.action_cards.remove(i); }
-            None => panic!("Action Card does not exist: {:?} in {:?}", card, self.player),
+            Some(i) => { self.action_cards.remove(i); Ok(()) }
+            None => Err(RuleViolation::CardNotInHand { player: self.player.clone(), card: card }),
         }
     }
 
-    pub fn change_reserve(&mut self, reserve_type: &ReserveType, diff: i8) {
+    pub fn change_reserve(&mut self, reserve_type: &ReserveType, diff: i8) -> Result<(), RuleViolation> {
         let current_value = *self.reserve.get(reserve_type).unwrap();
         let new_value = (current_value as i8) + diff;
-        if new_value < 0 {panic!("Cannot take {:?} reserves from {:?} {:?}", -diff, current_value, reserve_type)}
+        if new_value < 0 {return Err(RuleViolation::NotEnoughReserve { player: self.player.clone(), reserve_type: reserve_type.clone(), needed: (-diff) as u8, available: current_value })}
 
         self.reserve.insert(reserve_type.clone(), new_value as u8);
+        Ok(())
     }
 
     pub fn remove_resource(&self, resource_slot: u8, target_resource: &ResourceType) -> PlayerArea {
@@ -205,13 +222,9 @@ impl PlayerArea {
         }
     }
 
-    pub fn update_resource_slots(&self) -> (PlayerArea, Vec<ResourceType>) {
-        let blocked_slots = match self.reserve.get(&ReserveType::Cities).unwrap() {
-            5 => [false, false, true, true, true, true],
-            4 => [false, false, false, true, true, true],
-            3 => [false, false, false, false, true, true],
-            _ => [false, false, false, false, false, false],
-        };
+    pub fn update_resource_slots(&self, rules: &RuleConfig) -> (PlayerArea, Vec<ResourceType>) {
+        let open_slots = rules.open_slots(*self.reserve.get(&ReserveType::Cities).unwrap());
+        let blocked_slots = (0..self.resource_slots.len()).map(|i| i >= open_slots as usize);
         let (new_resource_slots, overflow_resources): (Vec<_>, Vec<_>) = self.resource_slots.iter().zip(blocked_slots).map(|(slot, blocked)| {
             match (slot, blocked) {
                 (ResourceSlot::Used { keys, resource }, true)                          => (ResourceSlot::Covered { keys: *keys }, Some(resource.clone())),
@@ -223,17 +236,78 @@ impl PlayerArea {
         (PlayerArea { resource_slots: new_resource_slots, ..self.clone() }, overflow_resources.into_iter().filter_map(|o| o).collect())
     }
 
+    // Powers of the leader and all lore of the player
+    pub fn powers(&self) -> Vec<Power> {
+        self.leader.iter().map(|l| l.power.clone()).chain(self.lore.iter().map(|l| l.power.clone())).collect()
+    }
+
+    // Keys a raider has to spend to take the target, None if there is nothing to take
+    pub fn raid_cost(&self, target: &RaidTarget) -> Option<u8> {
+        let extra_keys: u8 = self.powers().iter().map(|p| match p {
+            Power::ExtraKeys { keys } => *keys,
+            _ => 0
+        }).sum();
+        let keys = match target {
+            RaidTarget::Resource { resource_id } => match self.resource_slots.get(*resource_id as usize) {
+                Some(ResourceSlot::Used { keys, .. }) => Some(*keys),
+                _ => None
+            },
+            RaidTarget::GuildCard { card_id } => self.guild_cards.get(*card_id as usize).map(|g| g.keys)
+        };
+        keys.map(|k| k + extra_keys)
+    }
+
+    pub fn total_raid_cost(&self, targets: &[RaidTarget]) -> u8 {
+        if targets.iter().tuple_combinations().any(|(a, b)| a == b) {panic!("Cannot raid the same target twice in {:?}", targets)}
+        targets.iter().map(|t| self.raid_cost(t).unwrap_or_else(|| panic!("Cannot raid {:?} from {:?}", t, self.player))).sum()
+    }
+
+    // Every held resource and guild card with its keys, the safest first
+    pub fn raid_targets(&self) -> Vec<(RaidTarget, u8)> {
+        let resources = (0..self.resource_slots.len() as u8).map(|i| RaidTarget::Resource { resource_id: i });
+        let guild_cards = (0..self.guild_cards.len() as u8).map(|i| RaidTarget::GuildCard { card_id: i });
+        resources.chain(guild_cards)
+            .filter_map(|t| self.raid_cost(&t).map(|k| (t, k)))
+            .sorted_by_key(|(_, k)| std::cmp::Reverse(*k))
+            .collect()
+    }
+
+    // Total keys protecting the held resources of each type, guild cards included
+    pub fn resource_keys(&self) -> HashMap<ResourceType, u8> {
+        let slots = self.resource_slots.iter().filter_map(|s| match s {
+            ResourceSlot::Used { keys, resource } => Some((resource.clone(), *keys)),
+            _ => None
+        });
+        let guild_cards = self.guild_cards.iter().map(|g| (g.resource.clone(), g.keys));
+        slots.chain(guild_cards).fold(HashMap::new(), |mut acc, (resource, keys)| {
+            *acc.entry(resource).or_insert(0) += keys;
+            acc
+        })
+    }
+
+    // Indices of the slots a resource can be allocated to, the best protected first
+    pub fn open_slots_by_keys(&self) -> Vec<u8> {
+        self.resource_slots.iter().enumerate()
+            .filter_map(|(i, s)| match s {
+                ResourceSlot::Used { keys, .. } | ResourceSlot::Unused { keys } => Some((i as u8, *keys)),
+                ResourceSlot::Covered { .. } => None
+            })
+            .sorted_by_key(|(_, k)| std::cmp::Reverse(*k))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
     pub fn get_resource_count(&self, resource: ResourceType) -> u8 {
         let guild_resources = self.guild_cards.iter().filter(|g| g.resource == resource).count();
         let resources = self.resource_slots.iter().filter(|s| match s {
-            ResourceSlot::Used { keys: _, resource: _resource } => true,
+            ResourceSlot::Used { resource: r, .. } => *r == resource,
             _ => false
         }).count();
         (guild_resources+resources) as u8
     }
 
     pub fn get_trophies(&self) -> u8 {
-        self.tropies.iter().map(|t| t.count).sum()
+        self.trophies.iter().map(|t| t.count).sum()
     }
 
     pub fn get_captives(&self) -> u8 {
@@ -242,7 +316,7 @@ impl PlayerArea {
 
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AmbitionMarker{
     pub first_place: u8,
     pub second_place: u8,
@@ -251,7 +325,7 @@ pub struct AmbitionMarker{
     pub second_place_flipped: u8
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AmbitionTypes {
     Tycoon,
     Tyrant,
@@ -260,34 +334,40 @@ pub enum AmbitionTypes {
     Empath
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Ambition{
     pub ambition_type: AmbitionTypes,
     pub markers: Vec<AmbitionMarker>,
     pub discarded_resources: Vec<ResourceType>
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TurnState {
     TrickTaking,
     Prelude {action_type: ActionType, pips_left: u8},
     Actions {action_type: ActionType, pips_left: u8},
     AllocateResources {player: Color, resources: Vec<ResourceType>},
-    AllocateDiceResults {target_system: u8, target_player: Color, self_hits: u8, hits: u8, building_hits: u8, keys: u8}
+    AllocateDiceResults {target_system: u8, target_player: Color, self_hits: u8, hits: u8, building_hits: u8, keys: u8},
+    GameOver
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GameState {
     pub players: HashMap<Color, PlayerArea>,
     pub current_player: Color,
     pub players_in_round: u8,
     pub initiative: Color,
+    // Colours in clockwise seat order starting with the first player
+    pub seating: Vec<Color>,
     pub seized: Option<Color>,
     pub zero_marker: bool,
     pub turn_state: TurnState,
     pub next_turn_states: Vec<TurnState>,
     pub chapter: u8,
-    pub systems: Vec<System>,
+    pub rules: RuleConfig,
+    pub action_deck: Vec<ActionCard>,
+    // Change systems only through set_system and update_systems, which keep control up to date
+    pub(crate) systems: Vec<System>,
     pub resource_reserve: HashMap<ResourceType, u8>,
     pub court: Vec<CourtCard>,
     pub court_draw_pile: Vec<CourtCard>,
@@ -296,10 +376,54 @@ pub struct GameState {
     pub lead_card: Option<(ActionCard, bool, Color)>,
     pub follow_cards: Vec<(ActionCard, bool, Color)>,
     pub ambition_markers: Vec<AmbitionMarker>,
-    pub ambitions: HashMap<AmbitionTypes, Ambition>
+    pub ambitions: HashMap<AmbitionTypes, Ambition>,
+    pub reach: Reach,
+    pub events: Vec<GameEvent>,
+    // Every dice roll and shuffle after setup draws from here
+    pub rng: GameRng,
+    // None outside of a Blighted Reach campaign
+    pub campaign: Option<CampaignState>
 }
 
 impl GameState {
+    pub fn systems(&self) -> &[System] {
+        &self.systems
+    }
+
+    pub fn control_report(&self, system_id: u8) -> Option<ControlReport> {
+        self.systems.get(system_id as usize).and_then(|s| s.control_report())
+    }
+
+    // Systems named in an action are read through here, so ids outside the Reach in play are rejected
+    pub fn get_system(&self, system_id: u8) -> Result<&System, RuleViolation> {
+        if !self.reach.is_in_play(system_id) {return Err(RuleViolation::UnusedSystem { system_id: system_id })}
+        Ok(&self.systems[system_id as usize])
+    }
+
+    pub fn check_seated(&self, player: &Color) -> Result<(), RuleViolation> {
+        if !self.seating.contains(player) {return Err(RuleViolation::UnknownPlayer { player: player.clone() })}
+        Ok(())
+    }
+
+    pub fn set_system(&mut self, system_id: u8, system: System) {
+        self.systems[system_id as usize] = system.update_control();
+    }
+
+    pub fn update_systems(&mut self, f: impl Fn(&System) -> System) {
+        self.systems = self.systems.iter().map(|s| f(s).update_control()).collect();
+    }
+
+    // Ids of all systems whose stored controller differs from the one derived from their pieces
+    pub fn stale_control(&self) -> Vec<u8> {
+        self.systems.iter().enumerate().filter(|(_, s)| **s != s.update_control()).map(|(i, _)| i as u8).collect()
+    }
+
+    // The player seated clockwise of the given one
+    pub fn next_player(&self, color: &Color) -> Color {
+        let seat = self.seating.iter().position(|c| c == color).unwrap_or_else(|| panic!("{:?} is not seated at the table", color));
+        self.seating[(seat + 1) % self.seating.len()].clone()
+    }
+
     pub fn get_player_area(&self, color: &Color) -> PlayerArea {
         self.players.get(color).expect("Cannot find PlayerArea").clone()
     }
@@ -310,30 +434,89 @@ impl GameState {
         self.players.insert(color.clone(), player_area);
     }
 
-    pub fn update_players_reserve(&mut self, player: &Color, reserve_type: &ReserveType, diff: i8) {
+    pub fn update_players_reserve(&mut self, player: &Color, reserve_type: &ReserveType, diff: i8) -> Result<(), RuleViolation> {
         let mut current_area = self.players.get(player).unwrap().clone();
-        current_area.change_reserve(reserve_type, diff);
+        current_area.change_reserve(reserve_type, diff)?;
         self.players.insert(player.clone(), current_area.clone());
+        Ok(())
+    }
+
+    // Cities taken from or returned to the reserve uncover or cover ResourceSlots,
+    // resources on covered slots have to be allocated again by their owner.
+    // Building calls this when a City is placed. Losing a City only happens through building hits,
+    // so it waits on allocate_dice_results, which does not apply hits yet.
+    pub fn change_cities_in_reserve(&self, player: &Color, diff: i8) -> Result<GameState, RuleViolation> {
+        let mut new_game_state = self.clone();
+        new_game_state.update_players_reserve(player, &ReserveType::Cities, diff)?;
+        let (player_area, overflow) = new_game_state.get_player_area(player).update_resource_slots(&self.rules);
+        new_game_state.players.insert(player.clone(), player_area);
+
+        if !overflow.is_empty() {
+            new_game_state.next_turn_states.insert(0, new_game_state.turn_state.clone());
+            new_game_state.turn_state = TurnState::AllocateResources { player: player.clone(), resources: overflow };
+        }
+        Ok(new_game_state)
+    }
+
+    // The game ends after the last chapter, or earlier once a player reached the power to win
+    pub fn is_game_over(&self) -> bool {
+        self.chapter > self.rules.chapters || self.rules.power_to_win.is_some_and(|p| self.players.values().any(|a| a.power >= p))
+    }
+
+    // Deals a full hand to every player from the action deck
+    pub fn deal_action_cards(&self) -> GameState {
+        let hand_size = self.rules.hand_size as usize;
+        if self.action_deck.len() < hand_size * self.players.len() {panic!("Cannot deal {:?} cards to {:?} players from an action deck of {:?}", hand_size, self.players.len(), self.action_deck.len())}
+
+        let mut new_game_state = self.clone();
+        for player in self.seating.iter() {
+            let cards: Vec<ActionCard> = new_game_state.action_deck.drain(0..hand_size).collect();
+            new_game_state.add_action_cards(player, cards);
+        }
+        new_game_state
     }
 
     pub fn redraw_court_cards(&self) -> GameState {
-        let max_court_cards = if self.players.len() == 2 {3} else {4};
-        let new_court = if self.court.len() < max_court_cards {
-            self.court.iter().chain(vec![&self.court_draw_pile[0]]).cloned().collect()
-        } else {self.court.clone()};
-
-        let new_draw_pile = if self.court.len() < max_court_cards {
-            self.court_draw_pile.iter().skip(1).cloned().collect()
-        } else {self.court_draw_pile.clone()};
-
-        let new_game_state = GameState {
-            court: new_court.clone(),
-            court_draw_pile: new_draw_pile.clone(),
+        let max_court_cards = self.rules.court_size;
+        let mut new_game_state = self.clone();
+
+        while new_game_state.court.len() < max_court_cards {
+            if new_game_state.court_draw_pile.is_empty() {
+                if new_game_state.court_discard_pile.is_empty() {
+                    new_game_state.events.push(GameEvent::CourtNotRefilled {
+                        court_size: new_game_state.court.len() as u8,
+                        missing: (max_court_cards - new_game_state.court.len()) as u8
+                    });
+                    break;
+                }
+                new_game_state = new_game_state.reshuffle_court_discard_pile();
+            }
+            let card = new_game_state.court_draw_pile.remove(0);
+            new_game_state.court.push(card);
+        }
+
+        new_game_state
+    }
+
+    pub fn reshuffle_court_discard_pile(&self) -> GameState {
+        let discarded: Vec<CourtCard> = self.court_discard_pile.iter().map(|c| c.clear_agents()).collect();
+        let (new_draw_pile, rng) = self.rng.shuffle(&discarded);
+
+        let mut new_game_state = GameState {
+            court_draw_pile: self.court_draw_pile.iter().cloned().chain(new_draw_pile).collect(),
+            court_discard_pile: vec![],
+            rng: rng,
             .. self.clone()
         };
+        new_game_state.events.push(GameEvent::CourtDiscardReshuffled { cards: self.court_discard_pile.len() as u8 });
+        new_game_state
+    }
 
-        if new_court.len() == max_court_cards || new_draw_pile.len() == 0 {return new_game_state;} else {return  new_game_state.redraw_court_cards();}
-        
+    pub fn discard_court_card(&self, card_id: u8) -> GameState {
+        let mut new_game_state = self.clone();
+        let card = new_game_state.court.remove(card_id as usize);
+        new_game_state.court_discard_pile.push(card.clear_agents());
+        new_game_state
     }
 
     pub fn remove_resource(&self, player: &Color, resource_slot: u8, target_resource: ResourceType) -> GameState {
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+use super::game_state::{Color, ResourceType};
+use super::rng::GameRng;
+use super::system::BuildingType;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Power {
+    // In the prelude the first resource can be spent, where the second one is needed
+    SubstituteResource {used: ResourceType, counts_as: ResourceType},
+    // Raiding any held resource or guild card costs additional keys
+    ExtraKeys {keys: u8},
+    // Building a City also places a fresh Ship from the reserve in its system
+    ShipWithCity
+}
+
+// Pieces placed on the A, B and C locations during setup
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartingPieces {
+    pub a_ships: u8,
+    pub a_building: BuildingType,
+    pub b_ships: u8,
+    pub b_building: BuildingType,
+    pub c_ships: u8
+}
+
+impl Default for StartingPieces {
+    fn default() -> StartingPieces {
+        StartingPieces { a_ships: 3, a_building: BuildingType::City, b_ships: 3, b_building: BuildingType::Starport, c_ships: 2 }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leader {
+    pub title: String,
+    pub description: String,
+    pub resources: (ResourceType, ResourceType),
+    pub starting_pieces: StartingPieces,
+    pub power: Power
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lore {
+    pub title: String,
+    pub description: String,
+    pub power: Power
+}
+
+pub fn create_leaders() -> Vec<Leader> {
+    vec![
+        Leader {
+            title: "Elder".to_string(),
+            description: "Starts with Relics and Material. Raiding your resources costs 1 additional key.".to_string(),
+            resources: (ResourceType::Relics, ResourceType::Material),
+            starting_pieces: StartingPieces::default(),
+            power: Power::ExtraKeys { keys: 1 }
+        },
+        Leader {
+            title: "Mystic".to_string(),
+            description: "Starts with Psionics and Relics. You may spend Relics as Fuel in the prelude.".to_string(),
+            resources: (ResourceType::Psionics, ResourceType::Relics),
+            starting_pieces: StartingPieces { a_ships: 3, a_building: BuildingType::City, b_ships: 2, b_building: BuildingType::Starport, c_ships: 2 },
+            power: Power::SubstituteResource { used: ResourceType::Relics, counts_as: ResourceType::Fuel }
+        },
+        Leader {
+            title: "Fuel-Drinker".to_string(),
+            description: "Starts with Fuel and Fuel. You may spend Fuel as Material in the prelude.".to_string(),
+            resources: (ResourceType::Fuel, ResourceType::Fuel),
+            starting_pieces: StartingPieces { a_ships: 4, a_building: BuildingType::City, b_ships: 2, b_building: BuildingType::Starport, c_ships: 2 },
+            power: Power::SubstituteResource { used: ResourceType::Fuel, counts_as: ResourceType::Material }
+        },
+        Leader {
+            title: "Upstart".to_string(),
+            description: "Starts with Material and Fuel and a Starport on the A location. Building a City also places a Ship there.".to_string(),
+            resources: (ResourceType::Material, ResourceType::Fuel),
+            starting_pieces: StartingPieces { a_ships: 3, a_building: BuildingType::Starport, b_ships: 3, b_building: BuildingType::City, c_ships: 2 },
+            power: Power::ShipWithCity
+        },
+        Leader {
+            title: "Rebel".to_string(),
+            description: "Starts with Weapons and Material. You may spend Weapons as Relics in the prelude.".to_string(),
+            resources: (ResourceType::Weapons, ResourceType::Material),
+            starting_pieces: StartingPieces { a_ships: 3, a_building: BuildingType::City, b_ships: 3, b_building: BuildingType::Starport, c_ships: 3 },
+            power: Power::SubstituteResource { used: ResourceType::Weapons, counts_as: ResourceType::Relics }
+        },
+        Leader {
+            title: "Feastbringer".to_string(),
+            description: "Starts with Material and Psionics. Raiding your resources costs 1 additional key.".to_string(),
+            resources: (ResourceType::Material, ResourceType::Psionics),
+            starting_pieces: StartingPieces { a_ships: 2, a_building: BuildingType::City, b_ships: 2, b_building: BuildingType::Starport, c_ships: 2 },
+            power: Power::ExtraKeys { keys: 1 }
+        }
+    ]
+}
+
+pub fn create_lore_deck() -> Vec<Lore> {
+    vec![
+        Lore {
+            title: "Living Structures".to_string(),
+            description: "You may spend Psionics as Material in the prelude.".to_string(),
+            power: Power::SubstituteResource { used: ResourceType::Psionics, counts_as: ResourceType::Material }
+        },
+        Lore {
+            title: "Sprinter Drives".to_string(),
+            description: "You may spend Material as Fuel in the prelude.".to_string(),
+            power: Power::SubstituteResource { used: ResourceType::Material, counts_as: ResourceType::Fuel }
+        },
+        Lore {
+            title: "Ancient Holdings".to_string(),
+            description: "You may spend Weapons as Relics in the prelude.".to_string(),
+            power: Power::SubstituteResource { used: ResourceType::Weapons, counts_as: ResourceType::Relics }
+        },
+        Lore {
+            title: "Mirror Plating".to_string(),
+            description: "Raiding your resources costs 1 additional key.".to_string(),
+            power: Power::ExtraKeys { keys: 1 }
+        },
+        Lore {
+            title: "Tool Priests".to_string(),
+            description: "Building a City also places a Ship in its system.".to_string(),
+            power: Power::ShipWithCity
+        },
+        Lore {
+            title: "Hidden Harbors".to_string(),
+            description: "You may spend Relics as Fuel in the prelude.".to_string(),
+            power: Power::SubstituteResource { used: ResourceType::Relics, counts_as: ResourceType::Fuel }
+        }
+    ]
+}
+
+// Leaders and Lore are drafted in reverse turn order from one more card than players of each
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Draft {
+    pub players: Vec<Color>,
+    pub leaders: Vec<Leader>,
+    pub lore: Vec<Lore>,
+    pub picks: Vec<(Color, Leader, Lore)>
+}
+
+impl Draft {
+    pub fn deal(players: Vec<Color>, rng: &GameRng) -> (Draft, GameRng) {
+        let (mut leaders, rng) = rng.shuffle(&create_leaders());
+        let (mut lore, rng) = rng.shuffle(&create_lore_deck());
+        leaders.truncate(players.len() + 1);
+        lore.truncate(players.len() + 1);
+        (Draft { players: players, leaders: leaders, lore: lore, picks: vec![] }, rng)
+    }
+
+    pub fn next_player(&self) -> Option<Color> {
+        self.players.iter().rev().nth(self.picks.len()).cloned()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.picks.len() == self.players.len()
+    }
+
+    pub fn pick(&self, leader_title: &str, lore_title: &str) -> Draft {
+        let player = self.next_player().expect("Cannot pick, because the draft is complete");
+        let leader = self.leaders.iter().position(|l| l.title == leader_title).unwrap_or_else(|| panic!("Leader {:?} is not offered in the draft", leader_title));
+        let lore = self.lore.iter().position(|l| l.title == lore_title).unwrap_or_else(|| panic!("Lore {:?} is not offered in the draft", lore_title));
+
+        let mut new_draft = self.clone();
+        let leader = new_draft.leaders.remove(leader);
+        let lore = new_draft.lore.remove(lore);
+        new_draft.picks.push((player, leader, lore));
+        new_draft
+    }
+
+    pub fn picks_of(&self, player: &Color) -> Option<(Leader, Lore)> {
+        self.picks.iter().find(|(c, _, _)| c == player).map(|(_, leader, lore)| (leader.clone(), lore.clone()))
+    }
+}
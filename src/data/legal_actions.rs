@@ -0,0 +1,164 @@
+use std::iter;
+
+use itertools::{iproduct, Itertools};
+use serde::{Deserialize, Serialize};
+
+use super::court_cards::VoxPayload;
+use super::game_state::{Action, BasicAction};
+
+// Fleet moves and catapults are described by their limits, listing every split of the ships grows exponentially
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShipMoves {
+    // Any non-empty selection of the fleets, each moving up to the given fresh and damaged ships. The fleets are listed
+    // along their shortest path, but may take any path through the passable gates, given with their neighbours.
+    Fleets {destination_id: u8, fleets: Vec<(Vec<u8>, u8, u8)>, gates: Vec<(u8, Vec<u8>)>},
+    // Up to the given fresh and damaged ships in total, spread over any of the gates and at most one of the planets
+    Catapult {origin_system: u8, fresh: u8, damaged: u8, gates: Vec<u8>, planets: Vec<u8>}
+}
+
+// Ship moves taken as main action, or as prelude action paid with the resource in the given slot
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShipMoveOptions {
+    pub used_resource: Option<u8>,
+    pub moves: ShipMoves
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegalActions {
+    // Every legal action except fleet moves and catapults
+    pub actions: Vec<Action>,
+    pub ship_moves: Vec<ShipMoveOptions>
+}
+
+// Ways to hand out up to ships ships to the given number of targets
+fn distributions(ships: u8, targets: usize) -> u64 {
+    (1..=targets as u64).fold(1, |n, k| n * (ships as u64 + k) / k)
+}
+
+// Sums the ships going the same way, the order of the parts does not matter to the engine
+fn totals<K: Clone + Eq + std::hash::Hash>(parts: impl Iterator<Item = (K, u8, u8)>) -> Vec<(K, u16, u16)> {
+    parts.into_group_map_by(|(k, _, _)| k.clone())
+        .into_iter()
+        .map(|(k, parts)| (k, parts.iter().map(|(_, f, _)| *f as u16).sum(), parts.iter().map(|(_, _, d)| *d as u16).sum()))
+        .collect()
+}
+
+// Lists the parts of an action the engine takes in any order the way they are generated
+fn canonical(action: &Action) -> Action {
+    let canonical_basic = |basic_action: &BasicAction| match basic_action {
+        BasicAction::Battle { target_system, target_player, dice } => BasicAction::Battle { target_system: *target_system, target_player: target_player.clone(), dice: dice.iter().cloned().sorted().collect() },
+        BasicAction::Secure { card_id, vox_payload: Some(VoxPayload::MassUprising { target_systems }) } => BasicAction::Secure {
+            card_id: *card_id,
+            vox_payload: Some(VoxPayload::MassUprising { target_systems: target_systems.iter().cloned().sorted().collect() })
+        },
+        _ => basic_action.clone()
+    };
+    match action {
+        Action::MainAction { basic_action } => Action::MainAction { basic_action: canonical_basic(basic_action) },
+        Action::PreludeResourceAction { basic_action, used_resource } => Action::PreludeResourceAction { basic_action: canonical_basic(basic_action), used_resource: *used_resource },
+        Action::AllocateResources { configuration } => Action::AllocateResources { configuration: configuration.iter().cloned().sorted_by_key(|(slot, _)| *slot).collect() },
+        _ => action.clone()
+    }
+}
+
+impl ShipMoves {
+    pub fn contains(&self, action: &BasicAction) -> bool {
+        match (self, action) {
+            (ShipMoves::Fleets { destination_id, fleets, gates }, BasicAction::MoveFleets { destination_id: d, fleets: chosen }) => {
+                let neighbours = |gate: &u8| gates.iter().find(|(g, _)| g == gate).map(|(_, n)| n);
+                // Only a fleet, whose shortest path has no gates, can go straight
+                let valid_path = |path: &Vec<u8>| fleets.iter().any(|(p, _, _)| p.first() == path.first() && (path.len() > 1 || p.len() == 1))
+                    && path.iter().skip(1).all(|g| neighbours(g).is_some())
+                    && path.iter().chain(iter::once(d)).tuple_windows().skip(1).all(|(a, b)| neighbours(a).is_some_and(|n| n.contains(b)))
+                    && path.get(1).is_none_or(|g| neighbours(g).is_some_and(|n| n.contains(&path[0])));
+
+                d == destination_id
+                    && !chosen.is_empty()
+                    && chosen.iter().all(|(path, f, d)| (*f, *d) != (0, 0) && valid_path(path))
+                    && totals(chosen.iter().map(|(path, f, d)| (path[0], *f, *d))).iter()
+                        .all(|(origin, f, d)| fleets.iter().any(|(p, fresh, damaged)| p[0] == *origin && *f <= *fresh as u16 && *d <= *damaged as u16))
+            },
+            (ShipMoves::Catapult { origin_system, fresh, damaged, gates, planets }, BasicAction::Catapult { origin_system: o, destination_systems }) => {
+                let targets = totals(destination_systems.iter().cloned());
+                o == origin_system
+                    && !destination_systems.is_empty()
+                    && destination_systems.iter().all(|(s, f, d)| (*f, *d) != (0, 0) && (gates.contains(s) || planets.contains(s)))
+                    && targets.iter().filter(|(s, _, _)| planets.contains(s)).count() <= 1
+                    && targets.iter().map(|(_, f, _)| *f).sum::<u16>() <= *fresh as u16
+                    && targets.iter().map(|(_, _, d)| *d).sum::<u16>() <= *damaged as u16
+            },
+            _ => false
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        match self {
+            ShipMoves::Fleets { fleets, .. } => fleets.iter()
+                .fold(1u64, |n, (_, f, d)| n.saturating_mul((*f as u64 + 1) * (*d as u64 + 1))) - 1,
+            ShipMoves::Catapult { fresh, damaged, gates, planets, .. } => {
+                let splits = |targets: usize| distributions(*fresh, targets).saturating_mul(distributions(*damaged, targets));
+                // Moves into a planet are the ones giving it at least one ship
+                (splits(gates.len()) - 1).saturating_add((planets.len() as u64).saturating_mul(splits(gates.len() + 1) - splits(gates.len())))
+            }
+        }
+    }
+
+    // Every member in the form it is generated, one after another
+    pub fn basic_actions(&self) -> Box<dyn Iterator<Item = BasicAction> + '_> {
+        match self {
+            ShipMoves::Fleets { destination_id, fleets, .. } => Box::new(fleets.iter()
+                .map(|(_, f, d)| iproduct!(0..=*f, 0..=*d).collect::<Vec<_>>())
+                .multi_cartesian_product()
+                .map(move |splits| fleets.iter().zip(splits)
+                    .filter(|(_, split)| *split != (0, 0))
+                    .map(|((path, _, _), (f, d))| (path.clone(), f, d))
+                    .collect::<Vec<_>>())
+                .filter(|chosen| !chosen.is_empty())
+                .map(move |chosen| BasicAction::MoveFleets { destination_id: *destination_id, fleets: chosen })),
+            ShipMoves::Catapult { origin_system, fresh, damaged, gates, planets } => Box::new(iter::once(None).chain(planets.iter().map(Some))
+                .flat_map(move |planet| {
+                    let targets: Vec<u8> = gates.iter().chain(planet).cloned().sorted().collect();
+                    targets.iter()
+                        .map(|_| iproduct!(0..=*fresh, 0..=*damaged).collect::<Vec<_>>())
+                        .multi_cartesian_product()
+                        .filter(move |splits| splits.iter().map(|(f, _)| *f as u16).sum::<u16>() <= *fresh as u16 && splits.iter().map(|(_, d)| *d as u16).sum::<u16>() <= *damaged as u16)
+                        .map(move |splits| targets.iter().zip(splits).filter(|(_, split)| *split != (0, 0)).map(|(s, (f, d))| (*s, f, d)).collect::<Vec<_>>())
+                        .filter(move |destinations| !destinations.is_empty() && planet.is_none_or(|p| destinations.iter().any(|(s, _, _)| s == p)))
+                })
+                .map(move |destinations| BasicAction::Catapult { origin_system: *origin_system, destination_systems: destinations }))
+        }
+    }
+}
+
+impl ShipMoveOptions {
+    pub fn action(&self, basic_action: BasicAction) -> Action {
+        match self.used_resource {
+            Some(slot) => Action::PreludeResourceAction { basic_action: basic_action, used_resource: slot },
+            None => Action::MainAction { basic_action: basic_action }
+        }
+    }
+
+    pub fn contains(&self, action: &Action) -> bool {
+        match action {
+            Action::MainAction { basic_action } => self.used_resource.is_none() && self.moves.contains(basic_action),
+            Action::PreludeResourceAction { basic_action, used_resource } => self.used_resource == Some(*used_resource) && self.moves.contains(basic_action),
+            _ => false
+        }
+    }
+}
+
+impl LegalActions {
+    // Accepts every form execute_action accepts, while each action is listed in one form only
+    pub fn contains(&self, action: &Action) -> bool {
+        self.actions.contains(&canonical(action)) || self.ship_moves.iter().any(|o| o.contains(action))
+    }
+
+    pub fn count(&self) -> u64 {
+        self.ship_moves.iter().fold(self.actions.len() as u64, |n, o| n.saturating_add(o.moves.count()))
+    }
+
+    // Every legal action, the ship moves are generated while iterating
+    pub fn iter(&self) -> impl Iterator<Item = Action> + '_ {
+        self.actions.iter().cloned().chain(self.ship_moves.iter().flat_map(|o| o.moves.basic_actions().map(move |b| o.action(b))))
+    }
+}
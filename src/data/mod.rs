@@ -1,4 +1,13 @@
+pub mod campaign;
 pub mod court_cards;
+pub mod events;
 pub mod game_state;
+pub mod leaders;
+pub mod legal_actions;
+pub mod preview;
+pub mod reach;
+pub mod rng;
+pub mod rules;
 pub mod setup_cards;
-pub mod system;
\ No newline at end of file
+pub mod system;
+pub mod violations;
@@ -0,0 +1,158 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::game_state::{Color, GameState, PlayerArea, ResourceSlot, ResourceType, TurnState};
+use super::system::{BuildingSlot, BuildingType, System};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShipChange {
+    pub system_id: u8,
+    pub player: Color,
+    pub fresh: i8,
+    pub damaged: i8
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildingChange {
+    pub system_id: u8,
+    pub player: Color,
+    pub building_type: BuildingType,
+    pub fresh: i8,
+    pub damaged: i8
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceChange {
+    pub player: Color,
+    pub resource: ResourceType,
+    pub change: i8
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ControlChange {
+    pub system_id: u8,
+    pub before: Option<Color>,
+    pub after: Option<Color>
+}
+
+// The turn state after an action. A dry run showing rolled dice or drawn cards would let players try out their luck.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NextTurnState {
+    Known {turn_state: TurnState},
+    DependsOnDice,
+    DependsOnDraw
+}
+
+// Predicted effects of an action, ordered by system and seat
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionPreview {
+    pub ships: Vec<ShipChange>,
+    pub buildings: Vec<BuildingChange>,
+    pub resources: Vec<ResourceChange>,
+    pub control: Vec<ControlChange>,
+    pub turn_state: NextTurnState
+}
+
+const RESOURCES: [ResourceType; 5] = [ResourceType::Fuel, ResourceType::Material, ResourceType::Weapons, ResourceType::Relics, ResourceType::Psionics];
+const BUILDINGS: [BuildingType; 2] = [BuildingType::City, BuildingType::Starport];
+
+fn ships(system: &System, player: &Color) -> (i8, i8) {
+    match system {
+        System::Used { ships, .. } => ships.get(player).map(|s| (s.fresh as i8, s.damaged as i8)).unwrap_or((0, 0)),
+        System::Unused => (0, 0)
+    }
+}
+
+// Tokens in the resource slots, resources of guild cards only move with their card
+fn resources(player_area: &PlayerArea, resource: &ResourceType) -> i8 {
+    player_area.resource_slots.iter().filter(|s| matches!(s, ResourceSlot::Used { resource: r, .. } if r == resource)).count() as i8
+}
+
+fn buildings(system: &System, player: &Color, building_type: &BuildingType) -> (i8, i8) {
+    match system {
+        System::Used { building_slots, .. } => building_slots.iter().fold((0, 0), |(fresh, damaged), b| match b {
+            BuildingSlot::Occupied { fresh: true, player: p, building_type: t, .. } if p == player && t == building_type => (fresh + 1, damaged),
+            BuildingSlot::Occupied { fresh: false, player: p, building_type: t, .. } if p == player && t == building_type => (fresh, damaged + 1),
+            _ => (fresh, damaged)
+        }),
+        System::Unused => (0, 0)
+    }
+}
+
+// Draws change the size of a draw pile, rolled dice have to be allocated next
+fn next_turn_state(before: &GameState, after: &GameState) -> NextTurnState {
+    if before.action_deck.len() != after.action_deck.len() || before.court_draw_pile.len() != after.court_draw_pile.len() {
+        NextTurnState::DependsOnDraw
+    } else if matches!(after.turn_state, TurnState::AllocateDiceResults { .. }) && before.turn_state != after.turn_state {
+        NextTurnState::DependsOnDice
+    } else {
+        NextTurnState::Known { turn_state: after.turn_state.clone() }
+    }
+}
+
+fn controller(system: &System) -> Option<Color> {
+    match system {
+        System::Used { controlled_by, .. } => controlled_by.clone(),
+        System::Unused => None
+    }
+}
+
+impl ActionPreview {
+    pub fn between(before: &GameState, after: &GameState) -> ActionPreview {
+        let systems: Vec<(u8, &System, &System)> = before.systems.iter().zip(after.systems.iter()).enumerate().map(|(i, (b, a))| (i as u8, b, a)).collect();
+
+        let ships = systems.iter()
+            .flat_map(|(system_id, b, a)| before.seating.iter().filter_map(move |player| {
+                let ((fresh_before, damaged_before), (fresh_after, damaged_after)) = (ships(b, player), ships(a, player));
+                if (fresh_before, damaged_before) == (fresh_after, damaged_after) {return None}
+                Some(ShipChange { system_id: *system_id, player: player.clone(), fresh: fresh_after - fresh_before, damaged: damaged_after - damaged_before })
+            }))
+            .collect();
+
+        let buildings = systems.iter()
+            .flat_map(|(system_id, b, a)| before.seating.iter().flat_map(move |player| BUILDINGS.iter().filter_map(move |building_type| {
+                let ((fresh_before, damaged_before), (fresh_after, damaged_after)) = (buildings(b, player, building_type), buildings(a, player, building_type));
+                if (fresh_before, damaged_before) == (fresh_after, damaged_after) {return None}
+                Some(BuildingChange { system_id: *system_id, player: player.clone(), building_type: building_type.clone(), fresh: fresh_after - fresh_before, damaged: damaged_after - damaged_before })
+            })))
+            .collect();
+
+        let resources = before.seating.iter()
+            .flat_map(|player| RESOURCES.iter().filter_map(move |resource| {
+                let change = resources(&after.get_player_area(player), resource) - resources(&before.get_player_area(player), resource);
+                if change == 0 {return None}
+                Some(ResourceChange { player: player.clone(), resource: resource.clone(), change: change })
+            }))
+            .collect();
+
+        let control = systems.iter()
+            .filter(|(_, b, a)| controller(b) != controller(a))
+            .map(|(system_id, b, a)| ControlChange { system_id: *system_id, before: controller(b), after: controller(a) })
+            .collect();
+
+        ActionPreview { ships: ships, buildings: buildings, resources: resources, control: control, turn_state: next_turn_state(before, after) }
+    }
+}
+
+impl fmt::Display for ActionPreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for s in &self.ships {
+            writeln!(f, "{:?} Ships in System {}: {:+} fresh, {:+} damaged", s.player, s.system_id, s.fresh, s.damaged)?;
+        }
+        for b in &self.buildings {
+            writeln!(f, "{:?} {:?} in System {}: {:+} fresh, {:+} damaged", b.player, b.building_type, b.system_id, b.fresh, b.damaged)?;
+        }
+        for r in &self.resources {
+            writeln!(f, "{:?} {:?}: {:+}", r.player, r.resource, r.change)?;
+        }
+        for c in &self.control {
+            writeln!(f, "Control of System {}: {:?} -> {:?}", c.system_id, c.before, c.after)?;
+        }
+        match &self.turn_state {
+            NextTurnState::Known { turn_state } => write!(f, "Next: {:?}", turn_state),
+            NextTurnState::DependsOnDice => write!(f, "Next: depends on the dice"),
+            NextTurnState::DependsOnDraw => write!(f, "Next: depends on the cards drawn")
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::system::{Cluster, System};
+
+pub const CLUSTERS: u8 = 6;
+pub const SYSTEMS: u8 = 24;
+
+// The map of the game. Gates have the ids 0 to 5 and share their id with their cluster,
+// the planets of cluster i have the ids 6+3i, 7+3i and 8+3i.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reach {
+    pub clusters: Vec<Cluster>,
+    connections: Vec<Vec<u8>>
+}
+
+impl Reach {
+    pub fn new(cluster_out_of_play: &[u8]) -> Reach {
+        let in_play = |cluster: u8| !cluster_out_of_play.contains(&cluster);
+
+        let gate_connections = (0..CLUSTERS).map(|i| {
+            if !in_play(i) {return vec![];}
+            // Gates form a ring, which skips clusters out of play
+            let before = (1..CLUSTERS).map(|j| (i + CLUSTERS - j) % CLUSTERS).find(|c| in_play(*c)).unwrap_or(i);
+            let after = (1..CLUSTERS).map(|j| (i + j) % CLUSTERS).find(|c| in_play(*c)).unwrap_or(i);
+            vec![before, after].into_iter().chain(Reach::cluster_planets(i)).collect()
+        });
+
+        let planet_connections = (0..3 * CLUSTERS).map(|i| {
+            let cluster = i / 3;
+            if !in_play(cluster) {return vec![];}
+            let mut connections = vec![cluster];
+
+            // Planets before
+            if (i == 5 || i == 14) && in_play((i + 1) / 3) {connections.push(i + 7)}
+            if i % 3 != 0 {connections.push(i + 5)}
+
+            // Planets after
+            if i % 3 != 2 {connections.push(i + 7)}
+            if (i == 6 || i == 15) && in_play((i - 1) / 3) {connections.push(i + 5)}
+            connections
+        });
+
+        Reach {
+            clusters: (0..CLUSTERS).filter(|c| in_play(*c)).map(|c| Cluster { gate_id: c, systems_id: vec![c].into_iter().chain(Reach::cluster_planets(c)).collect() }).collect(),
+            connections: gate_connections.chain(planet_connections).collect()
+        }
+    }
+
+    // Rebuilds the Reach of a running game, clusters are out of play if their gate is unused
+    pub fn from_systems(systems: &[System]) -> Reach {
+        let cluster_out_of_play: Vec<u8> = (0..CLUSTERS).filter(|c| systems[*c as usize] == System::Unused).collect();
+        Reach::new(&cluster_out_of_play)
+    }
+
+    fn cluster_planets(cluster: u8) -> Vec<u8> {
+        (6 + 3 * cluster..9 + 3 * cluster).collect()
+    }
+
+    pub fn cluster_of(system_id: u8) -> u8 {
+        match system_id {
+            0..CLUSTERS => system_id,
+            CLUSTERS..SYSTEMS => (system_id - CLUSTERS) / 3,
+            _ => panic!("System {:?} is not part of the Reach", system_id)
+        }
+    }
+
+    pub fn is_gate(system_id: u8) -> bool {
+        system_id < CLUSTERS
+    }
+
+    pub fn gate_of(system_id: u8) -> u8 {
+        Reach::cluster_of(system_id)
+    }
+
+    pub fn is_in_play(&self, system_id: u8) -> bool {
+        system_id < SYSTEMS && !self.connections[system_id as usize].is_empty()
+    }
+
+    pub fn systems(&self) -> Vec<u8> {
+        (0..SYSTEMS).filter(|s| self.is_in_play(*s)).collect()
+    }
+
+    pub fn gates(&self) -> Vec<u8> {
+        self.clusters.iter().map(|c| c.gate_id).collect()
+    }
+
+    pub fn planets(&self) -> Vec<u8> {
+        self.systems().into_iter().filter(|s| !Reach::is_gate(*s)).collect()
+    }
+
+    pub fn cluster(&self, cluster: u8) -> Option<&Cluster> {
+        self.clusters.iter().find(|c| c.gate_id == cluster)
+    }
+
+    pub fn systems_in_cluster(&self, cluster: u8) -> Vec<u8> {
+        self.cluster(cluster).map(|c| c.systems_id.clone()).unwrap_or_default()
+    }
+
+    pub fn neighbours(&self, system_id: u8) -> &Vec<u8> {
+        &self.connections[system_id as usize]
+    }
+
+    pub fn are_connected(&self, origin: u8, destination: u8) -> bool {
+        self.is_in_play(origin) && self.connections[origin as usize].contains(&destination)
+    }
+
+    pub fn distances_from(&self, start: u8) -> Vec<Option<u8>> {
+        self.search(start).into_iter().map(|s| s.map(|(d, _)| d)).collect()
+    }
+
+    pub fn distance(&self, origin: u8, destination: u8) -> Option<u8> {
+        self.distances_from(origin)[destination as usize]
+    }
+
+    // Shortest path including origin and destination, None if the destination cannot be reached
+    pub fn shortest_path(&self, origin: u8, destination: u8) -> Option<Vec<u8>> {
+        let search = self.search(origin);
+        search[destination as usize]?;
+        let mut path = vec![destination];
+        while let Some((_, Some(previous))) = search[*path.last().unwrap() as usize] {
+            path.push(previous);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // All systems reached from start, when ships may only continue their way through passable gates
+    pub fn reachable_through_gates(&self, start: u8, passable: impl Fn(u8) -> bool) -> Vec<u8> {
+        let mut stack = vec![start];
+        let mut reachable: Vec<u8> = vec![];
+        while let Some(current) = stack.pop() {
+            for next in self.neighbours(current) {
+                if *next == start || reachable.contains(next) {continue;}
+                reachable.push(*next);
+                if Reach::is_gate(*next) && passable(*next) {stack.push(*next);}
+            }
+        }
+        reachable.sort();
+        reachable
+    }
+
+    // Breadth first search storing the distance and the previous system for every reached system
+    fn search(&self, start: u8) -> Vec<Option<(u8, Option<u8>)>> {
+        let mut search: Vec<Option<(u8, Option<u8>)>> = vec![None; SYSTEMS as usize];
+        if !self.is_in_play(start) {return search;}
+        search[start as usize] = Some((0, None));
+        let mut queue = VecDeque::from(vec![start]);
+        while let Some(current) = queue.pop_front() {
+            let (distance, _) = search[current as usize].unwrap();
+            for next in self.neighbours(current) {
+                if search[*next as usize].is_none() {
+                    search[*next as usize] = Some((distance + 1, Some(current)));
+                    queue.push_back(*next);
+                }
+            }
+        }
+        search
+    }
+}
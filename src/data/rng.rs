@@ -0,0 +1,54 @@
+use rand_chacha::ChaCha8Rng;
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+// Random stream of a game, stored as seed and ChaCha word position so every roll and shuffle
+// can be reproduced from the initial seed and the list of actions
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRng {
+    pub seed: u64,
+    pub word_pos: u128
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> GameRng {
+        GameRng { seed: seed, word_pos: 0 }
+    }
+
+    fn stream(&self) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        rng.set_word_pos(self.word_pos);
+        rng
+    }
+
+    // Uniform number in 0..bound, rejecting the biased tail of the u32 range
+    fn below(rng: &mut ChaCha8Rng, bound: u32) -> u32 {
+        let zone = u32::MAX - u32::MAX % bound;
+        loop {
+            let value = rng.next_u32();
+            if value < zone {return value % bound}
+        }
+    }
+
+    pub fn roll(&self, sides: u32) -> (u32, GameRng) {
+        let (rolls, rng) = self.rolls(1, sides);
+        (rolls[0], rng)
+    }
+
+    pub fn rolls(&self, count: usize, sides: u32) -> (Vec<u32>, GameRng) {
+        let mut rng = self.stream();
+        let rolls = (0..count).map(|_| GameRng::below(&mut rng, sides)).collect();
+        (rolls, GameRng { word_pos: rng.get_word_pos(), ..self.clone() })
+    }
+
+    // Fisher-Yates shuffle
+    pub fn shuffle<T: Clone>(&self, items: &[T]) -> (Vec<T>, GameRng) {
+        let mut rng = self.stream();
+        let mut shuffled = items.to_vec();
+        for i in (1..shuffled.len()).rev() {
+            let j = GameRng::below(&mut rng, i as u32 + 1) as usize;
+            shuffled.swap(i, j);
+        }
+        (shuffled, GameRng { word_pos: rng.get_word_pos(), ..self.clone() })
+    }
+}
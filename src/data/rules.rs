@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use super::game_state::{ActionCard, ActionType, AmbitionMarker, AmbitionTypes};
+
+// Rules chosen when creating a game, house rules are expressed by changing single values
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleConfig {
+    pub court_size: usize,
+    // Open ResourceSlots indexed by the Cities left in reserve
+    pub open_slots: Vec<u8>,
+    pub chapters: u8,
+    // The game ends after the chapter in which a player reaches this power, None plays all chapters
+    pub power_to_win: Option<u8>,
+    pub ambition_markers: Vec<AmbitionMarker>,
+    pub remove_ones_and_sevens: bool,
+    pub hand_size: u8,
+    // Every player sees the action cards in all hands
+    pub open_hands: bool
+}
+
+impl RuleConfig {
+    pub fn for_players(players: usize) -> RuleConfig {
+        RuleConfig {
+            court_size: if players == 2 {3} else {4},
+            // The first three cities uncover one, one and two ResourceSlots, the last two cover ambition bonuses
+            open_slots: vec![6, 6, 6, 4, 3, 2],
+            chapters: 5,
+            power_to_win: Some(match players {
+                2 => 33,
+                3 => 30,
+                _ => 27
+            }),
+            ambition_markers: vec![
+                AmbitionMarker {first_place: 2, second_place: 0, flipped: false, first_place_flipped: 4, second_place_flipped: 2},
+                AmbitionMarker {first_place: 3, second_place: 2, flipped: false, first_place_flipped: 6, second_place_flipped: 4},
+                AmbitionMarker {first_place: 5, second_place: 3, flipped: false, first_place_flipped: 9, second_place_flipped: 5}
+            ],
+            remove_ones_and_sevens: players < 4,
+            hand_size: 6,
+            open_hands: false
+        }
+    }
+
+    pub fn open_slots(&self, cities_in_reserve: u8) -> u8 {
+        *self.open_slots.get(cities_in_reserve as usize).unwrap_or_else(|| panic!("Cannot have {:?} Cities in reserve", cities_in_reserve))
+    }
+}
+
+pub fn create_action_deck(rules: &RuleConfig) -> Vec<ActionCard> {
+    let suits = [(ActionType::Administration, 4), (ActionType::Agression, 3), (ActionType::Construction, 4), (ActionType::Mobilization, 4)];
+    let ambitions = [None, Some(AmbitionTypes::Tycoon), Some(AmbitionTypes::Tyrant), Some(AmbitionTypes::Warlord), Some(AmbitionTypes::Keeper), Some(AmbitionTypes::Empath), None];
+    suits.iter()
+        .flat_map(|(action_type, pips)| (1..=7).map(|number| ActionCard {
+            action_type: action_type.clone(),
+            number: number,
+            pips: *pips,
+            declared_ambition: ambitions[number as usize - 1].clone()
+        }))
+        .filter(|c| !rules.remove_ones_and_sevens || (c.number != 1 && c.number != 7))
+        .collect()
+}
@@ -1,3 +1,67 @@
+use std::fmt;
+
+use rand::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::create_reach;
+use crate::data::reach::{Reach, SYSTEMS};
+use crate::data::system::{System, SystemType};
+use crate::data::game_state::{Color, ResourceType};
+use crate::data::campaign::CampaignState;
+use crate::data::leaders::Draft;
+use crate::data::rules::RuleConfig;
+use crate::data::rng::GameRng;
+
+// How players sit around the table, turns pass clockwise along the seats
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Seating {
+    // Red, Blue, White and Yellow take the first seats
+    #[default]
+    ColorOrder,
+    // Colours picked by the players in clockwise seat order
+    Chosen {seats: Vec<Color>},
+    // The colours of the color order in a seat order shuffled by the random stream of the game
+    Random
+}
+
+impl Seating {
+    // Chosen seats need one distinct colour for every player
+    pub fn validate(&self, players: u8) -> Result<(), SetupError> {
+        match self {
+            Seating::Chosen { seats } if seats.len() != players as usize || seats.iter().enumerate().any(|(i, c)| seats[..i].contains(c)) => {
+                Err(SetupError::InvalidSeating { players: players, seats: seats.clone() })
+            },
+            _ => Ok(())
+        }
+    }
+
+    pub fn seats(&self, players: u8, rng: &GameRng) -> (Vec<Color>, GameRng) {
+        let color_order: Vec<Color> = vec![Color::Red, Color::Blue, Color::White, Color::Yellow].into_iter().take(players as usize).collect();
+        if let Err(error) = self.validate(players) {panic!("{}", error)}
+        match self {
+            Seating::ColorOrder => (color_order, rng.clone()),
+            Seating::Chosen { seats } => (seats.clone(), rng.clone()),
+            Seating::Random => rng.shuffle(&color_order)
+        }
+    }
+}
+
+// Variants chosen before setup, the default is the base game
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SetupOptions {
+    pub seating: Seating,
+    // Takes the first seat in turn order and starts with initiative, by default the first seat
+    pub first_player: Option<Color>,
+    // A completed draft of Leaders and Lore enables the Leaders and Lore variant
+    pub leaders_and_lore: Option<Draft>,
+    // Starts the next act of a campaign with the state carried over from the last one
+    pub campaign: Option<CampaignState>,
+    // House rules replacing the rules for the player count
+    pub rules: Option<RuleConfig>
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SetupCard {
     pub players: u8,
     pub cluster_out_of_play: Vec<u8>,
@@ -6,4 +70,223 @@ pub struct SetupCard {
     pub c_locations: Vec<u8>
 }
 
-pub fn two_player_frontiers() -> SetupCard {return SetupCard { players: 2, cluster_out_of_play: vec![0,5], a_locations: vec![20,12], b_locations: vec![17, 18], c_locations: vec![2,4,14,15]}}
\ No newline at end of file
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LocationType {
+    A,
+    B,
+    C
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SetupError {
+    InvalidPlayerCount {players: u8},
+    UnknownCluster {cluster: u8},
+    WrongLocationCount {location_type: LocationType, expected: usize, found: usize},
+    UnknownSystem {location_type: LocationType, system_id: u8},
+    LocationOutOfPlay {location_type: LocationType, system_id: u8, cluster: u8},
+    LocationCollision {system_id: u8},
+    GateAsPlanetLocation {location_type: LocationType, system_id: u8},
+    IncompleteDraft {players: Vec<Color>},
+    CampaignPlayers {campaign: Vec<Color>, players: Vec<Color>},
+    FinishedCampaign {act: u8, acts: u8},
+    InvalidSeating {players: u8, seats: Vec<Color>},
+    FirstPlayerNotSeated {player: Color, seats: Vec<Color>}
+}
+
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetupError::InvalidPlayerCount { players } => write!(f, "A game needs 2 to 4 players, not {}", players),
+            SetupError::UnknownCluster { cluster } => write!(f, "Cluster {} does not exist", cluster),
+            SetupError::WrongLocationCount { location_type, expected, found } => write!(f, "Expected {} {:?} locations, found {}", expected, location_type, found),
+            SetupError::UnknownSystem { location_type, system_id } => write!(f, "{:?} location {} is not a system of the Reach", location_type, system_id),
+            SetupError::LocationOutOfPlay { location_type, system_id, cluster } => write!(f, "{:?} location {} lies in cluster {}, which is out of play", location_type, system_id, cluster),
+            SetupError::LocationCollision { system_id } => write!(f, "System {} is used as more than one starting location", system_id),
+            SetupError::GateAsPlanetLocation { location_type, system_id } => write!(f, "{:?} location {} is a gate, but needs a planet", location_type, system_id),
+            SetupError::IncompleteDraft { players } => write!(f, "Cannot setup with an incomplete Leader and Lore draft for {:?}", players),
+            SetupError::CampaignPlayers { campaign, players } => write!(f, "Cannot setup a campaign act of {:?} with {:?}", campaign, players),
+            SetupError::FinishedCampaign { act, acts } => write!(f, "Cannot setup act {} of a campaign with {} acts", act, acts),
+            SetupError::InvalidSeating { players, seats } => write!(f, "Cannot seat {} players on {:?}", players, seats),
+            SetupError::FirstPlayerNotSeated { player, seats } => write!(f, "First player {:?} is not seated in {:?}", player, seats),
+        }
+    }
+}
+
+impl SetupCard {
+    pub fn validate(&self) -> Result<(), Vec<SetupError>> {
+        let mut errors = vec![];
+
+        if !(2..=4).contains(&self.players) {
+            errors.push(SetupError::InvalidPlayerCount { players: self.players });
+        }
+
+        errors.extend(self.cluster_out_of_play.iter().filter(|c| **c >= 6).map(|c| SetupError::UnknownCluster { cluster: *c }));
+
+        let locations = [
+            (LocationType::A, &self.a_locations, self.players as usize),
+            (LocationType::B, &self.b_locations, self.players as usize),
+            (LocationType::C, &self.c_locations, 2 * self.players as usize)
+        ];
+
+        for (location_type, systems, expected) in locations.iter() {
+            if systems.len() != *expected {
+                errors.push(SetupError::WrongLocationCount { location_type: location_type.clone(), expected: *expected, found: systems.len() });
+            }
+            for system_id in systems.iter() {
+                if *system_id >= SYSTEMS {
+                    errors.push(SetupError::UnknownSystem { location_type: location_type.clone(), system_id: *system_id });
+                    continue;
+                }
+                let cluster = Reach::cluster_of(*system_id);
+                if self.cluster_out_of_play.contains(&cluster) {
+                    errors.push(SetupError::LocationOutOfPlay { location_type: location_type.clone(), system_id: *system_id, cluster: cluster });
+                }
+                // A and B locations receive a building, which gates cannot hold
+                if Reach::is_gate(*system_id) && *location_type != LocationType::C {
+                    errors.push(SetupError::GateAsPlanetLocation { location_type: location_type.clone(), system_id: *system_id });
+                }
+            }
+        }
+
+        let all_locations: Vec<u8> = self.a_locations.iter().chain(&self.b_locations).chain(&self.c_locations).cloned().collect();
+        for (i, system_id) in all_locations.iter().enumerate() {
+            // Report every colliding system only once
+            if all_locations[..i].iter().filter(|s| *s == system_id).count() == 1 {
+                errors.push(SetupError::LocationCollision { system_id: *system_id });
+            }
+        }
+
+        if errors.is_empty() {Ok(())} else {Err(errors)}
+    }
+}
+
+pub fn two_player_frontiers() -> SetupCard {return SetupCard { players: 2, cluster_out_of_play: vec![0,5], a_locations: vec![20,12], b_locations: vec![17, 18], c_locations: vec![2,4,14,15]}}
+
+pub fn two_player_homelands() -> SetupCard {return SetupCard { players: 2, cluster_out_of_play: vec![1,4], a_locations: vec![6,22], b_locations: vec![8,23], c_locations: vec![2,3,14,17]}}
+
+pub fn two_player_mix_up() -> SetupCard {return SetupCard { players: 2, cluster_out_of_play: vec![2,5], a_locations: vec![9,18], b_locations: vec![16,11], c_locations: vec![0,4,7,19]}}
+
+pub fn three_player_frontiers() -> SetupCard {return SetupCard { players: 3, cluster_out_of_play: vec![0], a_locations: vec![12,17,22], b_locations: vec![11,20,23], c_locations: vec![1,3,5,13,16,19]}}
+
+pub fn three_player_homelands() -> SetupCard {return SetupCard { players: 3, cluster_out_of_play: vec![3], a_locations: vec![7,13,21], b_locations: vec![8,12,22], c_locations: vec![1,2,5,10,19,20]}}
+
+pub fn three_player_core_conflict() -> SetupCard {return SetupCard { players: 3, cluster_out_of_play: vec![5], a_locations: vec![6,11,18], b_locations: vec![9,16,20], c_locations: vec![0,2,3,4,14,15]}}
+
+pub fn three_player_mix_up() -> SetupCard {return SetupCard { players: 3, cluster_out_of_play: vec![1], a_locations: vec![8,14,20], b_locations: vec![15,21,6], c_locations: vec![0,3,5,13,17,23]}}
+
+pub fn four_player_frontiers() -> SetupCard {return SetupCard { players: 4, cluster_out_of_play: vec![], a_locations: vec![6,12,17,22], b_locations: vec![11,14,20,23], c_locations: vec![0,1,2,3,4,5,9,16]}}
+
+pub fn four_player_homelands() -> SetupCard {return SetupCard { players: 4, cluster_out_of_play: vec![], a_locations: vec![7,13,18,21], b_locations: vec![8,10,16,22], c_locations: vec![2,3,4,5,0,1,9,20]}}
+
+pub fn four_player_mix_up_1() -> SetupCard {return SetupCard { players: 4, cluster_out_of_play: vec![], a_locations: vec![9,15,20,6], b_locations: vec![10,17,23,12], c_locations: vec![1,3,5,0,2,4,14,21]}}
+
+pub fn four_player_mix_up_2() -> SetupCard {return SetupCard { players: 4, cluster_out_of_play: vec![], a_locations: vec![11,16,22,7], b_locations: vec![14,19,8,13], c_locations: vec![0,2,4,1,3,5,6,18]}}
+
+// Names of all official setup cards for the given player count
+pub fn setup_card_names(players: u8) -> Vec<&'static str> {
+    match players {
+        2 => vec!["Frontiers", "Homelands", "Mix Up"],
+        3 => vec!["Frontiers", "Homelands", "Core Conflict", "Mix Up"],
+        4 => vec!["Frontiers", "Homelands", "Mix Up 1", "Mix Up 2"],
+        _ => vec![]
+    }
+}
+
+pub fn get_setup_card(name: &str, players: u8) -> Option<SetupCard> {
+    match (name, players) {
+        ("Frontiers", 2) => Some(two_player_frontiers()),
+        ("Homelands", 2) => Some(two_player_homelands()),
+        ("Mix Up", 2) => Some(two_player_mix_up()),
+        ("Frontiers", 3) => Some(three_player_frontiers()),
+        ("Homelands", 3) => Some(three_player_homelands()),
+        ("Core Conflict", 3) => Some(three_player_core_conflict()),
+        ("Mix Up", 3) => Some(three_player_mix_up()),
+        ("Frontiers", 4) => Some(four_player_frontiers()),
+        ("Homelands", 4) => Some(four_player_homelands()),
+        ("Mix Up 1", 4) => Some(four_player_mix_up_1()),
+        ("Mix Up 2", 4) => Some(four_player_mix_up_2()),
+        _ => None
+    }
+}
+
+fn is_fair(distances: &Vec<Vec<Option<u8>>>, a_locations: &Vec<u8>) -> bool {
+    // The closest rival home is (almost) equally far away for every player
+    let rival_distances: Vec<u8> = a_locations.iter().map(|a| {
+        a_locations.iter().filter(|q| *q != a).map(|q| distances[*a as usize][*q as usize].unwrap_or(u8::MAX)).min().unwrap()
+    }).collect();
+    let (closest, farthest) = (*rival_distances.iter().min().unwrap(), *rival_distances.iter().max().unwrap());
+    closest >= 2 && farthest - closest <= 1
+}
+
+fn is_balanced(systems: &Vec<System>, players: u8, a_locations: &Vec<u8>, b_locations: &Vec<u8>) -> bool {
+    let resource = |s: u8| match &systems[s as usize] {
+        System::Used { system_type: SystemType::Planet { resource }, .. } => Some(resource.clone()),
+        _ => None
+    };
+    let starting_resources: Vec<(Option<ResourceType>, Option<ResourceType>)> = a_locations.iter().zip(b_locations).map(|(a, b)| (resource(*a), resource(*b))).collect();
+
+    // Every player starts with two different resources and no resource dominates the table
+    let max_per_resource = (2 * players as usize).div_ceil(5);
+    starting_resources.iter().all(|(a, b)| a.is_some() && b.is_some() && a != b)
+        && [ResourceType::Fuel, ResourceType::Material, ResourceType::Weapons, ResourceType::Relics, ResourceType::Psionics].iter().all(|r| {
+            starting_resources.iter().filter(|(a, b)| a.as_ref() == Some(r) || b.as_ref() == Some(r)).count() <= max_per_resource
+        })
+}
+
+// Creates a random, legal SetupCard for custom maps. The same players and seed always result in the same SetupCard
+pub fn generate_setup_card(players: u8, seed: u64) -> SetupCard {
+    if !(2..=4).contains(&players) {panic!("Cannot generate SetupCard for {:?} players", players)}
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    loop {
+        let mut clusters: Vec<u8> = (0..6).collect();
+        clusters.shuffle(&mut rng);
+        let mut cluster_out_of_play: Vec<u8> = clusters[..(4 - players as usize)].to_vec();
+        cluster_out_of_play.sort();
+
+        let systems = create_reach(&Seating::ColorOrder.seats(players, &GameRng::new(seed)).0, &SetupCard { players: players, cluster_out_of_play: cluster_out_of_play.clone(), a_locations: vec![], b_locations: vec![], c_locations: vec![] });
+        let reach = Reach::new(&cluster_out_of_play);
+        let distances: Vec<Vec<Option<u8>>> = (0..SYSTEMS).map(|s| reach.distances_from(s)).collect();
+        let distance = |a: u8, b: u8| distances[a as usize][b as usize].unwrap_or(u8::MAX);
+        let planets: Vec<u8> = reach.planets();
+        let all_systems: Vec<u8> = reach.systems();
+
+        // Try a few placements before choosing other clusters to remove
+        for _ in 0..200 {
+            // Each B location is drawn from the planets close to the A location of the same player
+            let mut a_locations: Vec<u8> = vec![];
+            let mut b_locations: Vec<u8> = vec![];
+            for _ in 0..players {
+                let free: Vec<u8> = planets.iter().filter(|s| !a_locations.contains(s) && !b_locations.contains(s)).cloned().collect();
+                let a = *free.choose(&mut rng).unwrap();
+                let candidates: Vec<u8> = free.iter().filter(|b| **b != a && distance(a, **b) <= 3).cloned().collect();
+                match candidates.choose(&mut rng) {
+                    Some(b) => {a_locations.push(a); b_locations.push(*b);},
+                    None => break
+                }
+            }
+            if a_locations.len() != players as usize || !is_balanced(&systems, players, &a_locations, &b_locations) || !is_fair(&distances, &a_locations) {continue;}
+
+            // C locations are handed out in turn order, each close to the A or B location of its player
+            let mut c_locations: Vec<u8> = vec![];
+            for i in 0..2 * players as usize {
+                let p = i % players as usize;
+                let candidates: Vec<u8> = all_systems.iter()
+                    .filter(|s| !a_locations.contains(s) && !b_locations.contains(s) && !c_locations.contains(s))
+                    .filter(|s| distance(a_locations[p], **s).min(distance(b_locations[p], **s)) <= 2)
+                    .cloned()
+                    .collect();
+                match candidates.choose(&mut rng) {
+                    Some(c) => c_locations.push(*c),
+                    None => break
+                }
+            }
+            if c_locations.len() != 2 * players as usize {continue;}
+
+            let setup_card = SetupCard { players: players, cluster_out_of_play: cluster_out_of_play.clone(), a_locations: a_locations, b_locations: b_locations, c_locations: c_locations };
+            if setup_card.validate().is_ok() {
+                return setup_card;
+            }
+        }
+    }
+}
@@ -1,20 +1,23 @@
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
+
 use super::game_state::{Color, ResourceType};
+use super::violations::RuleViolation;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Ships {
     pub fresh: u8,
     pub damaged: u8
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BuildingType {
     City,
     Starport
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BuildingSlot {
     Occupied {fresh: bool, player: Color, building_type: BuildingType, used: bool},
     Empty
@@ -43,13 +46,13 @@ impl BuildingSlot {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SystemType{
     Gate,
     Planet {resource: ResourceType}
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum System {
     Unused,
     Used {
@@ -63,7 +66,7 @@ pub enum System {
 }
 
 impl System {
-    pub fn use_building(&self, building: &BuildingType, player_color: &Color) -> System {
+    pub fn use_building(&self, building: &BuildingType, player_color: &Color) -> Result<System, RuleViolation> {
         match self {
             System::Unused => panic!("Cannot use Building in Unused System"),
             System::Used { system_id, system_type, building_slots, ships, controlled_by, connects_to } => {
@@ -75,15 +78,15 @@ impl System {
                 });
                 let building_position = match building_position {
                     Some(i) => i,
-                    None => panic!("Cannot find unused Buildingslot in System {:?} of type {:?}", system_id, building)
+                    None => return Err(RuleViolation::NoUnusedBuilding { system_id: *system_id, building_type: building.clone(), player: player_color.clone() })
                 };
-                System::Used { system_id: *system_id,
+                Ok(System::Used { system_id: *system_id,
                     system_type: system_type.clone(),
                     building_slots: building_slots.iter().enumerate().map(|(i,b)| if i == building_position {b.use_building()} else {b.clone()}).collect(),
                     ships: ships.clone(),
                     controlled_by: controlled_by.clone(),
                     connects_to: connects_to.clone() 
-                }
+                })
             },
         }
     }
@@ -105,13 +108,81 @@ impl System {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Presence {
+    pub player: Color,
+    pub fresh_ships: u8,
+    pub damaged_ships: u8,
+    pub fresh_buildings: u8,
+    pub damaged_buildings: u8
+}
+
+impl Presence {
+    // Any ship or building, fresh or damaged, gives presence in a system
+    pub fn has_presence(&self) -> bool {
+        self.fresh_ships + self.damaged_ships + self.fresh_buildings + self.damaged_buildings > 0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlReason {
+    MostFreshShips {fresh_ships: u8, runner_up: u8},
+    Tied {fresh_ships: u8, players: Vec<Color>},
+    NoFreshShips
+}
+
+// Who controls a system and why. Only fresh ships decide control, buildings and damaged ships only give presence.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ControlReport {
+    pub system_id: u8,
+    pub controlled_by: Option<Color>,
+    pub reason: ControlReason,
+    pub presence: Vec<Presence>
+}
+
 impl System{
+    pub fn presence(self: &System, player_color: &Color) -> Presence {
+        let (ships, building_slots) = match self {
+            System::Unused => return Presence { player: player_color.clone(), fresh_ships: 0, damaged_ships: 0, fresh_buildings: 0, damaged_buildings: 0 },
+            System::Used {ships, building_slots, ..} => (ships, building_slots)
+        };
+        let (fresh_ships, damaged_ships) = ships.get(player_color).map(|s| (s.fresh, s.damaged)).unwrap_or((0, 0));
+        let buildings: Vec<bool> = building_slots.iter().filter_map(|b| match b {
+            BuildingSlot::Occupied { fresh, player, .. } if player == player_color => Some(*fresh),
+            _ => None
+        }).collect();
+        Presence {
+            player: player_color.clone(),
+            fresh_ships: fresh_ships,
+            damaged_ships: damaged_ships,
+            fresh_buildings: buildings.iter().filter(|f| **f).count() as u8,
+            damaged_buildings: buildings.iter().filter(|f| !**f).count() as u8
+        }
+    }
+
     pub fn has_presence(self: &System, player_color: &Color) -> bool {
+        self.presence(player_color).has_presence()
+    }
+
+    pub fn control_report(self: &System) -> Option<ControlReport> {
         match self {
-            System::Unused => false,
-            System::Used {ships, ..} => {
-                let s =ships.get(player_color).unwrap();
-                s.damaged >= 1 || s.fresh >= 1
+            System::Unused => None,
+            System::Used { system_id, ships, .. } => {
+                let mut presence: Vec<Presence> = ships.keys().map(|c| self.presence(c)).collect();
+                // Ties are listed in color order, so the report does not depend on the order of the ships map
+                presence.sort_by_key(|p| (std::cmp::Reverse(p.fresh_ships), p.player.clone()));
+
+                let most_fresh_ships = presence.first().map(|p| p.fresh_ships).unwrap_or(0);
+                let leaders: Vec<Color> = presence.iter().filter(|p| p.fresh_ships == most_fresh_ships).map(|p| p.player.clone()).collect();
+                let (controlled_by, reason) = if most_fresh_ships == 0 {
+                    (None, ControlReason::NoFreshShips)
+                } else if leaders.len() > 1 {
+                    (None, ControlReason::Tied { fresh_ships: most_fresh_ships, players: leaders })
+                } else {
+                    (Some(leaders[0].clone()), ControlReason::MostFreshShips { fresh_ships: most_fresh_ships, runner_up: presence.get(1).map(|p| p.fresh_ships).unwrap_or(0) })
+                };
+
+                Some(ControlReport { system_id: *system_id, controlled_by: controlled_by, reason: reason, presence: presence })
             }
         }
     }
@@ -126,26 +197,12 @@ impl System{
                 ships,
                 connects_to, ..
             } => {
-                let (new_controlled_by, _) = ships.iter().fold((None, 0), |acc: (Option<Color>, u8), (c,s)| {
-                    match acc {
-                        (None, max_count) => if s.fresh > max_count {(Some(c.clone()), s.fresh)} else {(None, max_count)},
-                        (Some(max_player), max_count) => {
-                            if s.fresh > max_count {
-                                (Some(c.clone()), s.fresh)
-                            } else if s.fresh == max_count && c != &max_player && s.fresh != 0 {
-                                (None, max_count)
-                            } else {
-                                (Some(max_player.clone()), max_count)
-                            }
-                        }
-                    }
-                });
                 return System::Used {
                     system_id: system_id.clone(),
                     system_type: system_type.clone(),
                     building_slots: building_slots.clone(),
                     ships: ships.clone(),
-                    controlled_by: new_controlled_by,
+                    controlled_by: self.control_report().unwrap().controlled_by,
                     connects_to: connects_to.clone()
                 };
             }
@@ -156,7 +213,7 @@ impl System{
         match self {
             System::Unused => 0,
             System::Used { ships, .. } => {
-                ships.get(color).unwrap().fresh
+                ships.get(color).map(|s| s.fresh).unwrap_or(0)
             }
         }
     }
@@ -165,8 +222,7 @@ impl System{
         match self {
             System::Unused => 0,
             System::Used { ships, .. } => {
-                let s = ships.get(color).unwrap();
-                s.fresh + s.damaged
+                ships.get(color).map(|s| s.fresh + s.damaged).unwrap_or(0)
             }
         }
     }
@@ -194,8 +250,8 @@ impl System{
 
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Cluster {
-    gate_id: u8,
-    systems_id: Vec<u8>
+    pub gate_id: u8,
+    pub systems_id: Vec<u8>
 }
\ No newline at end of file
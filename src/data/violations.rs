@@ -0,0 +1,115 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::court_cards::VoxPayload;
+use super::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, BuildType, Color, ReserveType, ResourceType, TurnState};
+use super::system::BuildingType;
+
+// Reasons an action is rejected by the engine, the game state stays unchanged
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuleViolation {
+    WrongTurnState {turn_state: TurnState, action: Action},
+    NoActionPips {turn_state: TurnState},
+    NotOnActionCard {action_type: ActionType, action: BasicAction},
+    WrongPreludeResource {action: BasicAction, resource: Option<ResourceType>, lead: ActionType},
+    NotImplemented {action: Action},
+    NoLeadCard,
+    LeadCardPlayed {lead: ActionCard},
+    CardNotInHand {player: Color, card: ActionCard},
+    CannotDeclareAmbition {card: ActionCard, ambition: AmbitionTypes},
+    NoAmbitionMarker,
+    CannotSurpass {card: ActionCard, lead: ActionCard},
+    CannotPivot {card: ActionCard, lead: ActionCard},
+    UnusedSystem {system_id: u8},
+    UnknownPlayer {player: Color},
+    NoPresence {system_id: u8, player: Color},
+    FreshBlight {system_id: u8},
+    NoBuildingSlots {system_id: u8},
+    BuildingSlotsOccupied {system_id: u8},
+    NoUnusedBuilding {system_id: u8, building_type: BuildingType, player: Color},
+    NothingToRepair {system_id: u8, build_type: BuildType},
+    NotConnected {origin: u8, destination: u8},
+    GateNotPassable {system_id: u8},
+    NotEnoughShips {system_id: u8, fresh: u8, damaged: u8, fresh_present: u8, damaged_present: u8},
+    NoFleets,
+    ReturnToOrigin {system_id: u8},
+    NoShipsMoved,
+    NoLoyalStarport {system_id: u8},
+    NoDestination,
+    MoreThanOnePlanet {destinations: Vec<u8>},
+    Unreachable {origin: u8, destination: u8},
+    TooManyDice {system_id: u8, dice: u8, ships: u8},
+    NoDice,
+    CannotTargetSelf {player: Color},
+    CannotTaxGate {system_id: u8},
+    NotControlled {system_id: u8, player: Color},
+    NotEnoughReserve {player: Color, reserve_type: ReserveType, needed: u8, available: u8},
+    TooManyResources {resources: u8, slots: u8},
+    DuplicateSlot {slot: u8},
+    ResourceNotAvailable {resource: ResourceType, allocated: u8, available: u8},
+    SlotCovered {slot: u8},
+    CannotHitOwnBuildings,
+    HitsNotAllocated {expected: u8, allocated: u8},
+    UnknownCourtCard {card_id: u8},
+    CardNotControlled {card_id: u8, player: Color},
+    MissingVoxPayload {card_id: u8},
+    WrongVoxPayload {payload: VoxPayload},
+    WrongTargetCount {expected: u8, found: u8},
+    NotInOneCluster {systems: Vec<u8>}
+}
+
+impl fmt::Display for RuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleViolation::WrongTurnState { turn_state, action } => write!(f, "Cannot execute {:?} in {:?}", action, turn_state),
+            RuleViolation::NoActionPips { turn_state } => write!(f, "No Action pips left in {:?}", turn_state),
+            RuleViolation::NotOnActionCard { action_type, action } => write!(f, "Cannot execute {:?} with {:?} Action Card", action, action_type),
+            RuleViolation::WrongPreludeResource { action, resource, lead } => write!(f, "Cannot execute {:?} with {:?} resource and {:?} lead", action, resource, lead),
+            RuleViolation::NotImplemented { action } => write!(f, "{:?} is not implemented yet", action),
+            RuleViolation::NoLeadCard => write!(f, "No lead card has been played"),
+            RuleViolation::LeadCardPlayed { lead } => write!(f, "{:?} has already been led this round", lead),
+            RuleViolation::CardNotInHand { player, card } => write!(f, "Action Card {:?} is not in the hand of {:?}", card, player),
+            RuleViolation::CannotDeclareAmbition { card, ambition } => write!(f, "Cannot declare {:?} with {:?}", ambition, card),
+            RuleViolation::NoAmbitionMarker => write!(f, "Cannot declare, because no AmbitionMarker is available"),
+            RuleViolation::CannotSurpass { card, lead } => write!(f, "Cannot surpass {:?} with {:?}", lead, card),
+            RuleViolation::CannotPivot { card, lead } => write!(f, "Cannot pivot from {:?} with {:?} of the same type", lead, card),
+            RuleViolation::UnusedSystem { system_id } => write!(f, "System {} is unused", system_id),
+            RuleViolation::UnknownPlayer { player } => write!(f, "{:?} is not seated at the table", player),
+            RuleViolation::NoPresence { system_id, player } => write!(f, "{:?} has no presence in System {}", player, system_id),
+            RuleViolation::FreshBlight { system_id } => write!(f, "Cannot build in System {} with fresh Blight", system_id),
+            RuleViolation::NoBuildingSlots { system_id } => write!(f, "System {} has no building slots", system_id),
+            RuleViolation::BuildingSlotsOccupied { system_id } => write!(f, "All building slots of System {} are occupied", system_id),
+            RuleViolation::NoUnusedBuilding { system_id, building_type, player } => write!(f, "{:?} has no unused {:?} in System {}", player, building_type, system_id),
+            RuleViolation::NothingToRepair { system_id, build_type } => write!(f, "No damaged {:?} to repair in System {}", build_type, system_id),
+            RuleViolation::NotConnected { origin, destination } => write!(f, "System {} is not connected to System {}", destination, origin),
+            RuleViolation::GateNotPassable { system_id } => write!(f, "Cannot move through {}, only gates not controlled by a rival can be passed", system_id),
+            RuleViolation::NotEnoughShips { system_id, fresh, damaged, fresh_present, damaged_present } => write!(f, "Moving {} fresh and {} damaged Ships from {} when only {} fresh and {} damaged are present", fresh, damaged, system_id, fresh_present, damaged_present),
+            RuleViolation::NoFleets => write!(f, "Cannot move without fleets"),
+            RuleViolation::ReturnToOrigin { system_id } => write!(f, "Fleets from {} cannot move back into their origin", system_id),
+            RuleViolation::NoShipsMoved => write!(f, "Cannot move without ships"),
+            RuleViolation::NoLoyalStarport { system_id } => write!(f, "System {} has no loyal Starport", system_id),
+            RuleViolation::NoDestination => write!(f, "Cannot catapult without destination"),
+            RuleViolation::MoreThanOnePlanet { destinations } => write!(f, "Cannot catapult into more than one Planet System of {:?}", destinations),
+            RuleViolation::Unreachable { origin, destination } => write!(f, "Cannot catapult from {} into unreachable System {}", origin, destination),
+            RuleViolation::TooManyDice { system_id, dice, ships } => write!(f, "Cannot roll {} dice with {} ships in System {}", dice, ships, system_id),
+            RuleViolation::NoDice => write!(f, "Cannot battle without dice"),
+            RuleViolation::CannotTargetSelf { player } => write!(f, "{:?} cannot target their own pieces", player),
+            RuleViolation::CannotTaxGate { system_id } => write!(f, "Cannot tax Gate System {}", system_id),
+            RuleViolation::NotControlled { system_id, player } => write!(f, "{:?} does not control System {}", player, system_id),
+            RuleViolation::NotEnoughReserve { player, reserve_type, needed, available } => write!(f, "Cannot take {} {:?} from the reserve of {:?} holding {}", needed, reserve_type, player, available),
+            RuleViolation::TooManyResources { resources, slots } => write!(f, "Cannot allocate {} resources into {} available ResourceSlots", resources, slots),
+            RuleViolation::DuplicateSlot { slot } => write!(f, "ResourceSlot {} is allocated twice", slot),
+            RuleViolation::ResourceNotAvailable { resource, allocated, available } => write!(f, "Cannot allocate {} {:?}, when only {} are available", allocated, resource, available),
+            RuleViolation::SlotCovered { slot } => write!(f, "Cannot allocate Resource in Covered ResourceSlot {}", slot),
+            RuleViolation::CannotHitOwnBuildings => write!(f, "Cannot hit own buildings, when attacking"),
+            RuleViolation::HitsNotAllocated { expected, allocated } => write!(f, "Allocated {} of {} hits", allocated, expected),
+            RuleViolation::UnknownCourtCard { card_id } => write!(f, "There is no Court Card {}", card_id),
+            RuleViolation::CardNotControlled { card_id, player } => write!(f, "Can only secure controlled Card, {:?} does not control Court Card {}", player, card_id),
+            RuleViolation::MissingVoxPayload { card_id } => write!(f, "Securing Court Card {} requires a VoxPayload", card_id),
+            RuleViolation::WrongVoxPayload { payload } => write!(f, "Cannot resolve Vox with {:?}", payload),
+            RuleViolation::WrongTargetCount { expected, found } => write!(f, "Expected {} target systems, found {}", expected, found),
+            RuleViolation::NotInOneCluster { systems } => write!(f, "Systems {:?} are not all in the same cluster", systems),
+        }
+    }
+}
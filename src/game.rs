@@ -0,0 +1,93 @@
+use std::fmt;
+
+use crate::data::events::{CardPlay, GameEvent};
+use crate::data::game_state::{Action, ActionCard, GameState};
+use crate::data::setup_cards::{SetupCard, SetupError, SetupOptions};
+use crate::data::violations::RuleViolation;
+use crate::replay::GameRecord;
+use crate::view::{GameView, Viewer};
+
+// Why the last action cannot be taken back
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UndoBlocked {
+    NothingToUndo,
+    RandomnessUsed {action: Action},
+    CardsDrawn {action: Action, cards: u8},
+    CardRevealed {action: Action, card: ActionCard}
+}
+
+impl fmt::Display for UndoBlocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UndoBlocked::NothingToUndo => write!(f, "No action has been taken yet"),
+            UndoBlocked::RandomnessUsed { action } => write!(f, "Cannot undo {:?}, dice were rolled or cards shuffled", action),
+            UndoBlocked::CardsDrawn { action, cards } => write!(f, "Cannot undo {:?}, {} cards were drawn", action, cards),
+            UndoBlocked::CardRevealed { action, card } => write!(f, "Cannot undo {:?}, {:?} was revealed", action, card)
+        }
+    }
+}
+
+// A game in progress with snapshots of its states, so mistakes can be taken back.
+// Snapshots from before the last action which cannot be undone are dropped, as the game can never return to them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    pub record: GameRecord,
+    // Starts with the state before the last action which cannot be undone, or the initial state
+    pub states: Vec<GameState>
+}
+
+// Why the action leading from before to after cannot be taken back
+fn undo_blocked(before: &GameState, after: &GameState, action: &Action) -> Option<UndoBlocked> {
+    if before.rng != after.rng {return Some(UndoBlocked::RandomnessUsed { action: action.clone() })}
+    let drawn = (before.court_draw_pile.len() + before.action_deck.len()).saturating_sub(after.court_draw_pile.len() + after.action_deck.len());
+    if drawn > 0 {return Some(UndoBlocked::CardsDrawn { action: action.clone(), cards: drawn as u8 })}
+    // Copied cards are played face down and stay hidden
+    after.events[before.events.len()..].iter().find_map(|e| match e {
+        GameEvent::CardPlayed { card, play, .. } if *play != CardPlay::Copy => Some(UndoBlocked::CardRevealed { action: action.clone(), card: card.clone() }),
+        _ => None
+    })
+}
+
+impl Game {
+    pub fn new(setup_card: &SetupCard, seed: u64, options: &SetupOptions) -> Result<Game, Vec<SetupError>> {
+        let record = GameRecord::new(setup_card, seed, options)?;
+        let initial_state = record.initial_state().expect("Setup leads to the state it just recorded");
+        Ok(Game { record: record, states: vec![initial_state] })
+    }
+
+    pub fn state(&self) -> &GameState {
+        self.states.last().unwrap()
+    }
+
+    pub fn view(&self, viewer: &Viewer) -> GameView {
+        GameView::new(self.state(), viewer)
+    }
+
+    pub fn play(&self, action: Action) -> Result<Game, RuleViolation> {
+        let (record, new_game_state) = self.record.record(self.state(), action.clone())?;
+        let states = match undo_blocked(self.state(), &new_game_state, &action) {
+            Some(_) => vec![self.state().clone(), new_game_state],
+            None => self.states.iter().cloned().chain(vec![new_game_state]).collect()
+        };
+        Ok(Game { record: record, states: states })
+    }
+
+    // The last action can be taken back as long as it did not draw on the random stream and showed no hidden card
+    pub fn can_undo(&self) -> Result<(), UndoBlocked> {
+        let (before, after) = match self.states.as_slice() {
+            [.., before, after] => (before, after),
+            _ => return Err(UndoBlocked::NothingToUndo)
+        };
+        match undo_blocked(before, after, &self.record.actions.last().unwrap().action) {
+            Some(blocked) => Err(blocked),
+            None => Ok(())
+        }
+    }
+
+    pub fn undo(&self) -> Result<Game, UndoBlocked> {
+        self.can_undo()?;
+        let mut record = self.record.clone();
+        record.actions.pop();
+        Ok(Game { record: record, states: self.states[..self.states.len() - 1].to_vec() })
+    }
+}
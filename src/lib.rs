@@ -1,3 +1,7 @@
 pub mod data;
 pub mod board;
-pub mod actions;
\ No newline at end of file
+pub mod actions;
+pub mod replay;
+pub mod game;
+pub mod save;
+pub mod view;
\ No newline at end of file
@@ -10,7 +10,7 @@ fn main() {
 
     let mut found_seed = None;
     for i in 0..10000 {
-        let inital_game_state = setup_game_with_set_seed(&test_setup, i);
+        let inital_game_state = setup_game_with_set_seed(&test_setup, i).unwrap();
         let has_mass_uprising = inital_game_state.court.iter().any(|c| match c {
             data::court_cards::CourtCard::VoxCard { vox, .. } => vox.title == "Mass Uprising",
             _ => false,
@@ -27,7 +27,7 @@ fn main() {
     }
 
     for i in 0..50 {
-        let inital_game_state = setup_game_with_set_seed(&test_setup,i);
+        let inital_game_state = setup_game_with_set_seed(&test_setup,i).unwrap();
 
         println!("{:?}: {:?}", i, inital_game_state.court.iter().map(|c| match c {
             data::court_cards::CourtCard::VoxCard { vox, .. } => vox.title.clone(),
@@ -0,0 +1,121 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::actions::execute_action;
+use crate::board::setup_game_with_options;
+use crate::data::events::GameEvent;
+use crate::data::game_state::{Action, GameState};
+use crate::data::setup_cards::{SetupCard, SetupError, SetupOptions};
+use crate::data::violations::RuleViolation;
+
+// An action together with the events it caused and the digest of the state it led to when it was first played
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedAction {
+    pub action: Action,
+    pub events: Vec<GameEvent>,
+    pub digest: u64
+}
+
+// Everything needed to rebuild a game: setup, seed and every action in order
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub setup_card: SetupCard,
+    pub seed: u64,
+    pub options: SetupOptions,
+    pub initial_digest: u64,
+    pub actions: Vec<RecordedAction>
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Divergence {
+    InvalidSetup {errors: Vec<SetupError>},
+    DifferentInitialState {recorded: u64, replayed: u64},
+    Rejected {index: usize, action: Action, violation: RuleViolation},
+    DifferentEvents {index: usize, action: Action, recorded: Vec<GameEvent>, replayed: Vec<GameEvent>},
+    DifferentState {index: usize, action: Action, recorded: u64, replayed: u64}
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Divergence::InvalidSetup { errors } => write!(f, "Setup is invalid: {}", errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", ")),
+            Divergence::DifferentInitialState { recorded, replayed } => write!(f, "Setup leads to state {:x} instead of {:x}", replayed, recorded),
+            Divergence::Rejected { index, action, violation } => write!(f, "Action {} {:?} is rejected: {}", index, action, violation),
+            Divergence::DifferentEvents { index, action, recorded, replayed } => write!(f, "Action {} {:?} caused {:?} instead of {:?}", index, action, replayed, recorded),
+            Divergence::DifferentState { index, action, recorded, replayed } => write!(f, "Action {} {:?} leads to state {:x} instead of {:x}", index, action, replayed, recorded)
+        }
+    }
+}
+
+// FNV-1a hash of the serialized state. Maps are serialized through Value, which sorts their keys, so equal states have equal digests.
+pub fn digest(game_state: &GameState) -> u64 {
+    let json = serde_json::to_value(game_state).expect("GameState is always serializable").to_string();
+    json.bytes().fold(0xcbf29ce484222325, |hash, b| (hash ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+impl GameRecord {
+    pub fn new(setup_card: &SetupCard, seed: u64, options: &SetupOptions) -> Result<GameRecord, Vec<SetupError>> {
+        let initial_state = setup_game_with_options(setup_card, seed, options)?.deal_action_cards();
+        Ok(GameRecord { setup_card: setup_card.clone(), seed: seed, options: options.clone(), initial_digest: digest(&initial_state), actions: vec![] })
+    }
+
+    // The game state before the first action, with a hand dealt to every player, as long as setup still leads to the recorded state
+    pub fn initial_state(&self) -> Result<GameState, Box<Divergence>> {
+        let initial_state = setup_game_with_options(&self.setup_card, self.seed, &self.options)
+            .map_err(|errors| Divergence::InvalidSetup { errors: errors })?
+            .deal_action_cards();
+        let replayed = digest(&initial_state);
+        if replayed != self.initial_digest {
+            return Err(Box::new(Divergence::DifferentInitialState { recorded: self.initial_digest, replayed: replayed }))
+        }
+        Ok(initial_state)
+    }
+
+    // Executes the action on the current state of the game and records it
+    pub fn record(&self, game_state: &GameState, action: Action) -> Result<(GameRecord, GameState), RuleViolation> {
+        let new_game_state = execute_action(game_state, action.clone())?;
+        let recorded = RecordedAction { action: action, events: new_game_state.events[game_state.events.len()..].to_vec(), digest: digest(&new_game_state) };
+        Ok((GameRecord { actions: self.actions.iter().cloned().chain(vec![recorded]).collect(), ..self.clone() }, new_game_state))
+    }
+
+    // Rebuilds every state of the game, starting with the initial state, and stops at the first difference to the record:
+    // another initial state, or an action which is rejected, causes other events or leads to another state
+    pub fn replay(&self) -> Result<Vec<GameState>, Box<Divergence>> {
+        self.actions.iter().enumerate().try_fold(vec![self.initial_state()?], |mut states, (index, recorded)| {
+            let game_state = states.last().unwrap();
+            let new_game_state = execute_action(game_state, recorded.action.clone())
+                .map_err(|violation| Divergence::Rejected { index: index, action: recorded.action.clone(), violation: violation })?;
+            let replayed = new_game_state.events[game_state.events.len()..].to_vec();
+            if replayed != recorded.events {
+                return Err(Box::new(Divergence::DifferentEvents { index: index, action: recorded.action.clone(), recorded: recorded.events.clone(), replayed: replayed }))
+            }
+            let replayed_digest = digest(&new_game_state);
+            if replayed_digest != recorded.digest {
+                return Err(Box::new(Divergence::DifferentState { index: index, action: recorded.action.clone(), recorded: recorded.digest, replayed: replayed_digest }))
+            }
+            states.push(new_game_state);
+            Ok(states)
+        })
+    }
+
+    // The same record with the digests of the states as they are serialized now. Older versions serialized them
+    // differently, but the actions still have to be accepted and cause the recorded events.
+    pub fn redigested(&self) -> Result<GameRecord, Box<Divergence>> {
+        let record = GameRecord::new(&self.setup_card, self.seed, &self.options).map_err(|errors| Divergence::InvalidSetup { errors: errors })?;
+        let game_state = record.initial_state()?;
+        self.actions.iter().enumerate().try_fold((record, game_state), |(record, game_state), (index, recorded)| {
+            let (record, new_game_state) = record.record(&game_state, recorded.action.clone())
+                .map_err(|violation| Divergence::Rejected { index: index, action: recorded.action.clone(), violation: violation })?;
+            let replayed = &record.actions[index].events;
+            if *replayed != recorded.events {
+                return Err(Box::new(Divergence::DifferentEvents { index: index, action: recorded.action.clone(), recorded: recorded.events.clone(), replayed: replayed.clone() }))
+            }
+            Ok((record, new_game_state))
+        }).map(|(record, _)| record)
+    }
+
+    pub fn final_state(&self) -> Result<GameState, Box<Divergence>> {
+        self.replay().map(|states| states.last().unwrap().clone())
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::data::game_state::GameState;
+use crate::replay::{Divergence, GameRecord};
+
+// Raised whenever the serialized form of the data types changes, together with a migration from the previous version
+pub const SAVE_VERSION: u32 = 2;
+
+// Versioned JSON representation of a game
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub game_state: GameState
+}
+
+// Versioned JSON representation of a game record, sharing its versions and migrations with save files
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordFile {
+    pub version: u32,
+    pub record: GameRecord
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SaveError {
+    Malformed {message: String},
+    MissingVersion,
+    UnsupportedVersion {found: u32, supported: u32},
+    Diverged {divergence: Box<Divergence>}
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Malformed { message } => write!(f, "Cannot read save file: {}", message),
+            SaveError::MissingVersion => write!(f, "Save file has no version"),
+            SaveError::UnsupportedVersion { found, supported } => write!(f, "Save file version {} is not supported, the latest version is {}", found, supported),
+            SaveError::Diverged { divergence } => write!(f, "Saved record cannot be replayed: {}", divergence)
+        }
+    }
+}
+
+impl SaveFile {
+    pub fn new(game_state: &GameState) -> SaveFile {
+        SaveFile { version: SAVE_VERSION, game_state: game_state.clone() }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("GameState is always serializable")
+    }
+
+    pub fn from_json(json: &str) -> Result<SaveFile, SaveError> {
+        let value: Value = serde_json::from_str(json).map_err(|e| SaveError::Malformed { message: e.to_string() })?;
+        serde_json::from_value(migrate(value)?).map_err(|e| SaveError::Malformed { message: e.to_string() })
+    }
+}
+
+impl RecordFile {
+    pub fn new(record: &GameRecord) -> RecordFile {
+        RecordFile { version: SAVE_VERSION, record: record.clone() }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("GameRecord is always serializable")
+    }
+
+    // Version 1 stored the bare record without a version. The digests of migrated records are taken anew,
+    // as the states they were taken from were serialized differently.
+    pub fn from_json(json: &str) -> Result<RecordFile, SaveError> {
+        let value: Value = serde_json::from_str(json).map_err(|e| SaveError::Malformed { message: e.to_string() })?;
+        let value = if value.get("version").is_none() && value.get("actions").is_some() {
+            serde_json::json!({ "version": 1, "record": value })
+        } else {
+            value
+        };
+        let migrated = value.get("version").and_then(|v| v.as_u64()).is_some_and(|v| v < SAVE_VERSION as u64);
+        let record_file: RecordFile = serde_json::from_value(migrate(value)?).map_err(|e| SaveError::Malformed { message: e.to_string() })?;
+        if !migrated {return Ok(record_file)}
+        let record = record_file.record.redigested().map_err(|divergence| SaveError::Diverged { divergence: divergence })?;
+        Ok(RecordFile { record: record, ..record_file })
+    }
+}
+
+// Upgrades a save or record file of any older version step by step to the current version
+pub fn migrate(value: Value) -> Result<Value, SaveError> {
+    let version = value.get("version").and_then(|v| v.as_u64()).ok_or(SaveError::MissingVersion)? as u32;
+    if version > SAVE_VERSION {return Err(SaveError::UnsupportedVersion { found: version, supported: SAVE_VERSION })}
+    (version..SAVE_VERSION).try_fold(value, |value, from| {
+        let mut value = match from {
+            // Records hold no player areas
+            1 if value.get("record").is_some() => value,
+            1 => rename_trophies(value)?,
+            _ => return Err(SaveError::UnsupportedVersion { found: from, supported: SAVE_VERSION })
+        };
+        value["version"] = Value::from(from + 1);
+        Ok(value)
+    })
+}
+
+// Version 2 fixed the spelling of the trophies in the player areas
+fn rename_trophies(mut value: Value) -> Result<Value, SaveError> {
+    let players = value.pointer_mut("/game_state/players")
+        .and_then(|p| p.as_object_mut())
+        .ok_or(SaveError::Malformed { message: "missing players".to_string() })?;
+    for area in players.values_mut().filter_map(|a| a.as_object_mut()) {
+        if let Some(trophies) = area.remove("tropies") {area.insert("trophies".to_string(), trophies);}
+    }
+    Ok(value)
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::campaign::CampaignState;
+use crate::data::court_cards::{CourtCard, Guild};
+use crate::data::events::{CardPlay, GameEvent};
+use crate::data::game_state::{Action, ActionCard, Agents, Ambition, AmbitionMarker, AmbitionTypes, Color, GameState, PlayerArea, ReserveType, ResourceSlot, ResourceType, Trophy, TurnState};
+use crate::data::leaders::{Leader, Lore};
+use crate::data::reach::Reach;
+use crate::data::rules::RuleConfig;
+use crate::data::system::System;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Viewer {
+    Seat {player: Color},
+    Spectator
+}
+
+impl Viewer {
+    // Open hands show every card, so nothing a player holds or plays face down stays hidden
+    fn sees(&self, game_state: &GameState, player: &Color) -> bool {
+        game_state.rules.open_hands || *self == Viewer::Seat { player: player.clone() }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Hand {
+    Visible {cards: Vec<ActionCard>},
+    Hidden {count: usize}
+}
+
+// A card in the current trick, the card is None for face down cards of other players
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayedCard {
+    pub player: Color,
+    pub face_up: bool,
+    pub card: Option<ActionCard>
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventView {
+    Visible {event: GameEvent},
+    // Another player took an action naming a card played face down or used to seize
+    HiddenAction {player: Color},
+    HiddenCardPlayed {player: Color, play: CardPlay, seize: bool}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub player: Color,
+    pub power: u8,
+    pub initiative: bool,
+    pub hand: Hand,
+    pub guild_cards: Vec<Guild>,
+    pub reserve: HashMap<ReserveType,u8>,
+    pub resource_slots: Vec<ResourceSlot>,
+    pub captives: Vec<Agents>,
+    pub trophies: Vec<Trophy>,
+    pub leader: Option<Leader>,
+    pub lore: Vec<Lore>
+}
+
+// Everything one seat or a spectator is allowed to see of a game, the only form of the state sent to clients.
+// Draw piles become their sizes and the random stream is left out, as its seed predicts every roll and shuffle.
+// The action discard becomes its size as well, it keeps face down cards in play order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameView {
+    pub viewer: Viewer,
+    pub players: HashMap<Color, PlayerView>,
+    pub current_player: Color,
+    pub players_in_round: u8,
+    pub initiative: Color,
+    pub seating: Vec<Color>,
+    pub seized: Option<Color>,
+    pub zero_marker: bool,
+    pub turn_state: TurnState,
+    pub next_turn_states: Vec<TurnState>,
+    pub chapter: u8,
+    pub rules: RuleConfig,
+    pub action_deck: usize,
+    pub systems: Vec<System>,
+    pub resource_reserve: HashMap<ResourceType, u8>,
+    pub court: Vec<CourtCard>,
+    pub court_draw_pile: usize,
+    pub court_discard_pile: Vec<CourtCard>,
+    pub action_discard: usize,
+    pub lead_card: Option<PlayedCard>,
+    pub follow_cards: Vec<PlayedCard>,
+    pub ambition_markers: Vec<AmbitionMarker>,
+    pub ambitions: HashMap<AmbitionTypes, Ambition>,
+    pub reach: Reach,
+    pub events: Vec<EventView>,
+    pub campaign: Option<CampaignState>
+}
+
+fn player_view(game_state: &GameState, viewer: &Viewer, player_area: &PlayerArea) -> PlayerView {
+    let hand = if viewer.sees(game_state, &player_area.player) {
+        Hand::Visible { cards: player_area.action_cards.clone() }
+    } else {
+        Hand::Hidden { count: player_area.action_cards.len() }
+    };
+    PlayerView {
+        player: player_area.player.clone(),
+        power: player_area.power,
+        initiative: player_area.initiative,
+        hand: hand,
+        guild_cards: player_area.guild_cards.clone(),
+        reserve: player_area.reserve.clone(),
+        resource_slots: player_area.resource_slots.clone(),
+        captives: player_area.captives.clone(),
+        trophies: player_area.trophies.clone(),
+        leader: player_area.leader.clone(),
+        lore: player_area.lore.clone()
+    }
+}
+
+fn played_card(game_state: &GameState, viewer: &Viewer, (card, face_up, player): &(ActionCard, bool, Color)) -> PlayedCard {
+    let visible = *face_up || viewer.sees(game_state, player);
+    PlayedCard { player: player.clone(), face_up: *face_up, card: if visible {Some(card.clone())} else {None} }
+}
+
+fn event_view(game_state: &GameState, viewer: &Viewer, event: &GameEvent) -> EventView {
+    match event {
+        GameEvent::ActionTaken { player, action: Action::Copy { .. } | Action::Surpass { seize: Some(_), .. } | Action::Pivot { seize: Some(_), .. } }
+            if !viewer.sees(game_state, player) => EventView::HiddenAction { player: player.clone() },
+        GameEvent::CardPlayed { player, play: CardPlay::Copy, seize, .. } if !viewer.sees(game_state, player) =>
+            EventView::HiddenCardPlayed { player: player.clone(), play: CardPlay::Copy, seize: *seize },
+        _ => EventView::Visible { event: event.clone() }
+    }
+}
+
+impl GameView {
+    pub fn new(game_state: &GameState, viewer: &Viewer) -> GameView {
+        GameView {
+            viewer: viewer.clone(),
+            players: game_state.players.iter().map(|(color, area)| (color.clone(), player_view(game_state, viewer, area))).collect(),
+            current_player: game_state.current_player.clone(),
+            players_in_round: game_state.players_in_round,
+            initiative: game_state.initiative.clone(),
+            seating: game_state.seating.clone(),
+            seized: game_state.seized.clone(),
+            zero_marker: game_state.zero_marker,
+            turn_state: game_state.turn_state.clone(),
+            next_turn_states: game_state.next_turn_states.clone(),
+            chapter: game_state.chapter,
+            rules: game_state.rules.clone(),
+            action_deck: game_state.action_deck.len(),
+            systems: game_state.systems.clone(),
+            resource_reserve: game_state.resource_reserve.clone(),
+            court: game_state.court.clone(),
+            court_draw_pile: game_state.court_draw_pile.len(),
+            court_discard_pile: game_state.court_discard_pile.clone(),
+            action_discard: game_state.action_discard.len(),
+            lead_card: game_state.lead_card.as_ref().map(|c| played_card(game_state, viewer, c)),
+            follow_cards: game_state.follow_cards.iter().map(|c| played_card(game_state, viewer, c)).collect(),
+            ambition_markers: game_state.ambition_markers.clone(),
+            ambitions: game_state.ambitions.clone(),
+            reach: game_state.reach.clone(),
+            events: game_state.events.iter().map(|e| event_view(game_state, viewer, e)).collect(),
+            campaign: game_state.campaign.clone()
+        }
+    }
+}
@@ -6,22 +6,23 @@ mod test {
 
     use correspondence_arcs::board;
     use correspondence_arcs::actions;
+    use correspondence_arcs::data::violations::RuleViolation;
 
     #[test]
     fn roll_one_skirmish_dice() {
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
-        let game_state = actions::moving::move_ships(&game_state, 17, 16, 1, 0);
-        let game_state = actions::moving::move_ships(&game_state, 16, 15, 1, 0);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 1, 0).unwrap();
+        let game_state = actions::moving::move_ships(&game_state, 16, 15, 1, 0).unwrap();
 
         let new_game_state = actions::execute_actions(&game_state, vec![
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction { basic_action: BasicAction::Battle { target_system: 15, target_player: Color::Blue, dice: vec![Dice::Skirmish] }}
-        ]);
+        ]).unwrap();
 
         match new_game_state.turn_state {
             TurnState::AllocateDiceResults { target_system, target_player, self_hits, hits, building_hits, keys } => {
@@ -42,18 +43,18 @@ mod test {
     #[test]
     fn roll_three_skirmish_dice() {
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
-        let game_state = actions::moving::move_ships(&game_state, 17, 16, 3, 0);
-        let game_state = actions::moving::move_ships(&game_state, 16, 15, 3, 0);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 3, 0).unwrap();
+        let game_state = actions::moving::move_ships(&game_state, 16, 15, 3, 0).unwrap();
 
         let new_game_state = actions::execute_actions(&game_state, vec![
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction { basic_action: BasicAction::Battle { target_system: 15, target_player: Color::Blue, dice: vec![Dice::Skirmish,Dice::Skirmish,Dice::Skirmish] }}
-        ]);
+        ]).unwrap();
 
         match new_game_state.turn_state {
             TurnState::AllocateDiceResults { target_system, target_player, self_hits, hits, building_hits, keys } => {
@@ -75,38 +76,38 @@ mod test {
 
 
     #[test]
-    #[should_panic(expected="Cannot roll more dice than ships present")]
     fn roll_more_dice_than_ships(){
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
-        let game_state = actions::moving::move_ships(&game_state, 17, 16, 1, 0);
-        let game_state = actions::moving::move_ships(&game_state, 16, 15, 1, 0);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 1, 0).unwrap();
+        let game_state = actions::moving::move_ships(&game_state, 16, 15, 1, 0).unwrap();
 
-        let _ = actions::execute_actions(&game_state, vec![
+        let result = actions::execute_actions(&game_state, vec![
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction { basic_action: BasicAction::Battle { target_system: 15, target_player: Color::Blue, dice: vec![Dice::Skirmish,Dice::Skirmish] }}
         ]);
+        assert_eq!(result, Err(RuleViolation::TooManyDice { system_id: 15, dice: 2, ships: 1 }));
     }
 
     #[test]
-    #[should_panic(expected="Cannot battle Blue in System without presence.")]
     fn battle_without_target(){
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
-        let game_state = actions::moving::move_ships(&game_state, 17, 16, 1, 0);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 1, 0).unwrap();
 
-        let _ = actions::execute_actions(&game_state, vec![
+        let result = actions::execute_actions(&game_state, vec![
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction { basic_action: BasicAction::Battle { target_system: 16, target_player: Color::Blue, dice: vec![Dice::Skirmish] }}
         ]);
+        assert_eq!(result, Err(RuleViolation::NoPresence { system_id: 16, player: Color::Blue }));
     }
 
 }
\ No newline at end of file
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::{SetupCard, two_player_frontiers};
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, BuildType, Color, GameState, ReserveType, ResourceSlot, ResourceType, TurnState};
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+    use correspondence_arcs::data::violations::RuleViolation;
+
+    fn open_slots(game_state: &GameState, player: &Color) -> usize {
+        game_state.get_player_area(player).resource_slots.iter().filter(|s| !matches!(s, ResourceSlot::Covered { .. })).count()
+    }
+
+    #[test]
+    fn test_cities_in_reserve_cover_slots(){
+        let game_state: GameState = board::setup_game(&two_player_frontiers()).unwrap();
+
+        let expected = vec![(5, 2), (4, 3), (3, 4), (2, 6), (1, 6), (0, 6)];
+        for (cities, open) in expected {
+            let mut player_area = game_state.get_player_area(&Color::Red);
+            player_area.reserve.insert(ReserveType::Cities, cities);
+            let (player_area, _) = player_area.update_resource_slots(&game_state.rules);
+            assert_eq!(player_area.resource_slots.iter().filter(|s| !matches!(s, ResourceSlot::Covered { .. })).count(), open, "{:?} Cities in reserve", cities);
+        }
+    }
+
+    #[test]
+    fn test_resource_count_by_type(){
+        let mut player_area = board::setup_game_with_set_seed(&two_player_frontiers(), 3).unwrap().get_player_area(&Color::Red);
+        player_area.resource_slots = vec![
+            ResourceSlot::Used { keys: 3, resource: ResourceType::Fuel },
+            ResourceSlot::Used { keys: 1, resource: ResourceType::Psionics },
+            ResourceSlot::Used { keys: 1, resource: ResourceType::Psionics },
+            ResourceSlot::Unused { keys: 2 }
+        ];
+
+        assert_eq!(player_area.get_resource_count(ResourceType::Psionics), 2);
+        assert_eq!(player_area.get_resource_count(ResourceType::Fuel), 1);
+        assert_eq!(player_area.get_resource_count(ResourceType::Relics), 0);
+    }
+
+    #[test]
+    fn test_building_city_uncovers_slot(){
+        let test_setup: SetupCard = two_player_frontiers();
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
+        assert_eq!(open_slots(&game_state, &Color::Red), 3);
+
+        game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 2, 0).unwrap();
+
+        let new_game_state = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
+            Action::EndPrelude,
+            Action::MainAction {basic_action: BasicAction::Build { target_system: 16, build_type: BuildType::City }}
+        ]).unwrap();
+
+        let player_area = new_game_state.get_player_area(&Color::Red);
+        assert_eq!(player_area.reserve.get(&ReserveType::Cities), Some(&3));
+        assert_eq!(player_area.resource_slots[3], ResourceSlot::Unused { keys: 2 });
+        assert_eq!(open_slots(&new_game_state, &Color::Red), 4);
+        assert_eq!(new_game_state.turn_state, TurnState::Actions { action_type: ActionType::Construction, pips_left: 3 });
+    }
+
+    #[test]
+    fn test_losing_city_on_empty_slot(){
+        let game_state: GameState = board::setup_game(&two_player_frontiers()).unwrap();
+
+        let new_game_state = game_state.change_cities_in_reserve(&Color::Red, 1).unwrap();
+
+        assert_eq!(new_game_state.get_player_area(&Color::Red).resource_slots[2], ResourceSlot::Covered { keys: 1 });
+        assert_eq!(new_game_state.turn_state, game_state.turn_state);
+        assert_eq!(new_game_state.next_turn_states, vec![]);
+    }
+
+    #[test]
+    fn test_losing_city_overflows_resources(){
+        let mut game_state: GameState = board::setup_game(&two_player_frontiers()).unwrap();
+        let mut player_area = game_state.get_player_area(&Color::Red);
+        player_area.resource_slots[2] = ResourceSlot::Used { keys: 1, resource: ResourceType::Fuel };
+        game_state.players.insert(Color::Red, player_area);
+        game_state.turn_state = TurnState::Actions { action_type: ActionType::Agression, pips_left: 2 };
+
+        let g1 = game_state.change_cities_in_reserve(&Color::Red, 1).unwrap();
+
+        assert_eq!(g1.get_player_area(&Color::Red).resource_slots[2], ResourceSlot::Covered { keys: 1 });
+        assert_eq!(g1.turn_state, TurnState::AllocateResources { player: Color::Red, resources: vec![ResourceType::Fuel] });
+        assert_eq!(g1.next_turn_states, vec![TurnState::Actions { action_type: ActionType::Agression, pips_left: 2 }]);
+
+        // Keep the Fuel instead of the Material, which goes back to the reserve
+        let g2 = actions::execute_action(&g1, Action::AllocateResources { configuration: vec![(0, ResourceType::Psionics), (1, ResourceType::Fuel)] }).unwrap();
+
+        assert_eq!(
+            g2.get_player_area(&Color::Red).resource_slots,
+            vec![ResourceSlot::Used { keys: 3, resource: ResourceType::Psionics }, ResourceSlot::Used { keys: 1, resource: ResourceType::Fuel }, ResourceSlot::Covered { keys: 1 }, ResourceSlot::Covered { keys: 2 }, ResourceSlot::Covered { keys: 1 }, ResourceSlot::Covered { keys: 3 }]
+        );
+        assert_eq!(g2.resource_reserve.get(&ResourceType::Material), Some(&(game_state.resource_reserve.get(&ResourceType::Material).unwrap() + 1)));
+        assert_eq!(g2.turn_state, TurnState::Actions { action_type: ActionType::Agression, pips_left: 2 });
+        assert_eq!(g2.next_turn_states, vec![]);
+    }
+
+    #[test]
+    fn test_losing_city_during_turn_resumes_actions(){
+        let card = ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Warlord) };
+        let mut game_state: GameState = board::setup_game(&two_player_frontiers()).unwrap();
+        game_state.add_action_cards(&Color::Red, vec![card.clone()]);
+        let mut player_area = game_state.get_player_area(&Color::Red);
+        player_area.resource_slots[2] = ResourceSlot::Used { keys: 1, resource: ResourceType::Fuel };
+        game_state.players.insert(Color::Red, player_area);
+
+        let g1 = actions::execute_actions(&game_state, vec![Action::PlayLeadCard { card: card, declare: None }, Action::EndPrelude]).unwrap();
+        let g2 = g1.change_cities_in_reserve(&Color::Red, 1).unwrap();
+        assert_eq!(g2.turn_state, TurnState::AllocateResources { player: Color::Red, resources: vec![ResourceType::Fuel] });
+
+        let g3 = actions::execute_action(&g2, Action::AllocateResources { configuration: vec![(0, ResourceType::Fuel), (1, ResourceType::Material)] }).unwrap();
+        assert_eq!(g3.turn_state, g1.turn_state);
+
+        let g4 = actions::execute_action(&g3, Action::MainAction { basic_action: BasicAction::Move { origin_id: 17, destination_id: 3, fresh_ships: 1, damaged_ships: 0 } }).unwrap();
+        assert_eq!(g4.turn_state, TurnState::Actions { action_type: ActionType::Agression, pips_left: 2 });
+        assert_eq!(g4.get_player_area(&Color::Red).resource_slots[2], ResourceSlot::Covered { keys: 1 });
+    }
+
+    #[test]
+    fn test_building_city_without_cities_in_reserve(){
+        let game_state: GameState = board::setup_game(&two_player_frontiers()).unwrap();
+        assert_eq!(game_state.change_cities_in_reserve(&Color::Red, -5), Err(RuleViolation::NotEnoughReserve { player: Color::Red, reserve_type: ReserveType::Cities, needed: 5, available: 4 }));
+    }
+}
@@ -9,11 +9,12 @@ mod test{
     
     use correspondence_arcs::board;
     use correspondence_arcs::actions;
+    use correspondence_arcs::data::violations::RuleViolation;
 
     #[test]
     fn test_building_ships(){
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
@@ -24,9 +25,9 @@ mod test{
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Build { target_system: target_system, build_type: build_type }}
-        ]);
+        ]).unwrap();
 
-        match &new_game_state.systems[target_system as usize] {
+        match &new_game_state.systems()[target_system as usize] {
             System::Used { system_id, system_type, building_slots, ships, controlled_by, connects_to } => {
                 assert_eq!(system_id, &17);
                 assert_eq!(system_type, &SystemType::Planet { resource: ResourceType::Material });
@@ -42,7 +43,7 @@ mod test{
     #[test]
     fn test_execute_building_action(){
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
@@ -53,9 +54,9 @@ mod test{
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Build { target_system: target_system, build_type: build_type }}
-        ]);
+        ]).unwrap();
 
-        match &new_game_state.systems[target_system as usize] {
+        match &new_game_state.systems()[target_system as usize] {
             System::Used {ships, controlled_by, ..} => {
                 assert_eq!(ships.clone(), [(Color::Red, Ships { fresh: 4, damaged: 0 }), (Color::Blue, Ships { fresh: 0, damaged: 0 })].iter().cloned().collect::<HashMap<Color,Ships>>());
                 assert_eq!(controlled_by, &Some(Color::Red));
@@ -67,22 +68,22 @@ mod test{
     #[test]
     fn test_building_city(){
         let test_setup: SetupCard = SetupCard { players: 2, cluster_out_of_play: vec![0,5], a_locations: vec![20,11], b_locations: vec![17, 18], c_locations: vec![2,4,14,15]};
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
         let target_system = 16;
         let build_type = BuildType::City;
 
-        let game_state = actions::moving::move_ships(&game_state, 17, 16, 2, 0);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 2, 0).unwrap();
 
         let new_game_state = actions::execute_actions(&game_state, vec![
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Build { target_system: target_system, build_type: build_type }}
-        ]);      
+        ]).unwrap();      
 
-        match &new_game_state.systems[target_system as usize] {
+        match &new_game_state.systems()[target_system as usize] {
             System::Used {building_slots, controlled_by, ..} => {
                 assert_eq!(building_slots, &vec![BuildingSlot::Occupied { fresh: true, player: Color::Red, building_type: BuildingType::City, used: false}, BuildingSlot::Empty]);
                 assert_eq!(controlled_by, &Some(Color::Red));
@@ -94,23 +95,23 @@ mod test{
     #[test]
     fn building_starport(){
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
         let target_system: u8 = 15;
         let build_type = BuildType::Starport;
 
-        let g1 = actions::moving::move_ships(&game_state,17, 16, 2, 0);
-        let g2 = actions::moving::move_ships(&g1,16,15, 2, 0);
+        let g1 = actions::moving::move_ships(&game_state,17, 16, 2, 0).unwrap();
+        let g2 = actions::moving::move_ships(&g1,16,15, 2, 0).unwrap();
         
         let new_game_state = actions::execute_actions(&g2, vec![
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Build { target_system: target_system, build_type: build_type }}
-        ]);
+        ]).unwrap();
 
-        match &new_game_state.systems[target_system as usize] {
+        match &new_game_state.systems()[target_system as usize] {
             System::Used {building_slots, controlled_by, ..} => {
                 assert_eq!(building_slots, &vec![BuildingSlot::Occupied { fresh: true, player: Color::Red, building_type: BuildingType::Starport, used: false}, BuildingSlot::Empty]);
                 //Should be controlled by no one, because of two blue ships
@@ -121,39 +122,39 @@ mod test{
     }
 
     #[test]
-    #[should_panic(expected = "No building slots available")]
     fn building_in_gate(){
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
         let target_system = 3;
         let build_type = BuildType::Starport;
 
-        let g1 = actions::moving::move_ships(&game_state, 17, 3, 2, 0);
-        let _ = actions::execute_actions(&g1, vec![
+        let g1 = actions::moving::move_ships(&game_state, 17, 3, 2, 0).unwrap();
+        let result = actions::execute_actions(&g1, vec![
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Build { target_system: target_system, build_type: build_type }}
         ]);
+        assert_eq!(result, Err(RuleViolation::NoBuildingSlots { system_id: 3 }));
     }
 
     #[test]
-    #[should_panic(expected = "All building slots are occupied")]
     fn build_in_full_system(){
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
         let target_system = 17;
         let build_type = BuildType::Starport;
 
-        let _ = actions::execute_actions(&game_state, vec![
+        let result = actions::execute_actions(&game_state, vec![
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Build { target_system: target_system, build_type: build_type }}
         ]);
+        assert_eq!(result, Err(RuleViolation::BuildingSlotsOccupied { system_id: 17 }));
     }
 }
\ No newline at end of file
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::{two_player_frontiers, three_player_frontiers, SetupError, SetupOptions};
+
+    use correspondence_arcs::data::campaign::{self, CampaignState, Fate, Objective, Status};
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, BuildType, Color, GameState};
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+    use correspondence_arcs::data::violations::RuleViolation;
+    use correspondence_arcs::data::rng::GameRng;
+
+    fn setup_act(campaign: &CampaignState) -> GameState {
+        board::setup_game_with_options(&two_player_frontiers(), 0, &SetupOptions { campaign: Some(campaign.clone()), ..SetupOptions::default() }).unwrap()
+    }
+
+    #[test]
+    fn test_new_campaign(){
+        let campaign = CampaignState::new(vec![Color::Red, Color::Blue, Color::White], 3, &GameRng::new(7)).0;
+
+        assert_eq!(campaign.act, 1);
+        assert_eq!(campaign.regent(), Some(Color::Red));
+        assert_eq!(campaign.get_player(&Color::Blue).status, Status::Imperial);
+        assert_ne!(campaign.get_player(&Color::Red).fate, campaign.get_player(&Color::Blue).fate);
+        assert_ne!(campaign.get_player(&Color::Blue).fate, campaign.get_player(&Color::White).fate);
+        assert_eq!(campaign, CampaignState::new(vec![Color::Red, Color::Blue, Color::White], 3, &GameRng::new(7)).0);
+    }
+
+    #[test]
+    fn test_status_changes(){
+        let campaign = CampaignState::new(vec![Color::Red, Color::Blue], 3, &GameRng::new(7)).0
+            .set_status(&Color::Red, Status::Outlaw)
+            .set_status(&Color::Blue, Status::Regent);
+
+        assert_eq!(campaign.get_player(&Color::Red).status, Status::Outlaw);
+        assert_eq!(campaign.regent(), Some(Color::Blue));
+    }
+
+    #[test]
+    #[should_panic(expected = "while Red is Regent")]
+    fn test_only_one_regent(){
+        CampaignState::new(vec![Color::Red, Color::Blue], 3, &GameRng::new(7)).0.set_status(&Color::Blue, Status::Regent);
+    }
+
+    #[test]
+    fn test_setup_from_campaign(){
+        let campaign = CampaignState::new(vec![Color::Red, Color::Blue], 3, &GameRng::new(7)).0.place_blight(16);
+        let game_state = setup_act(&campaign);
+
+        assert_eq!(game_state.campaign, Some(campaign));
+        assert_eq!(board::setup_game_with_set_seed(&two_player_frontiers(), 0).unwrap().campaign, None);
+    }
+
+    #[test]
+    fn test_setup_from_campaign_with_other_players(){
+        let campaign = CampaignState::new(vec![Color::Red, Color::Blue], 3, &GameRng::new(7)).0;
+        let errors = board::setup_game_with_options(&three_player_frontiers(), 0, &SetupOptions { campaign: Some(campaign), ..SetupOptions::default() }).unwrap_err();
+        assert_eq!(errors, vec![SetupError::CampaignPlayers { campaign: vec![Color::Red, Color::Blue], players: vec![Color::Red, Color::Blue, Color::White] }]);
+    }
+
+    #[test]
+    fn test_blight(){
+        let campaign = CampaignState::new(vec![Color::Red, Color::Blue], 3, &GameRng::new(7)).0.place_blight(16).place_blight(16).place_blight(3);
+        assert_eq!(campaign.blight_in(16).len(), 2);
+
+        let campaign = campaign.remove_blight(16);
+        assert_eq!(campaign.blight_in(16).len(), 1);
+        assert_eq!(campaign.blight_in(3).len(), 1);
+        assert_eq!(campaign.blight_in(17).len(), 0);
+    }
+
+    #[test]
+    fn test_build_in_blighted_system(){
+        let campaign = CampaignState::new(vec![Color::Red, Color::Blue], 3, &GameRng::new(7)).0.place_blight(16);
+        let mut game_state = setup_act(&campaign);
+        game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 2, 0).unwrap();
+
+        let result = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: BasicAction::Build { target_system: 16, build_type: BuildType::City } }
+        ]);
+        assert_eq!(result, Err(RuleViolation::FreshBlight { system_id: 16 }));
+    }
+
+    #[test]
+    fn test_objectives_and_acts(){
+        let mut campaign = CampaignState::new(vec![Color::Red, Color::Blue], 2, &GameRng::new(7)).0;
+        campaign.players.get_mut(&Color::Red).unwrap().fate = Fate { title: "Steward".to_string(), description: "".to_string(), objective: Objective::ControlSystems { systems: 4 } };
+        campaign.players.get_mut(&Color::Blue).unwrap().fate = Fate { title: "Advocate".to_string(), description: "".to_string(), objective: Objective::HavePower { power: 10 } };
+        let mut game_state = setup_act(&campaign);
+
+        let checked = campaign::check_objectives(&game_state);
+        assert!(checked.campaign.as_ref().unwrap().get_player(&Color::Red).objective_met);
+        assert!(!checked.campaign.as_ref().unwrap().get_player(&Color::Blue).objective_met);
+
+        game_state.players.get_mut(&Color::Blue).unwrap().power = 6;
+        let next_act = campaign::end_act(&game_state);
+        assert_eq!(next_act.act, 2);
+        assert_eq!(next_act.get_player(&Color::Blue).power, 6);
+        assert!(!next_act.get_player(&Color::Red).objective_met);
+        assert_eq!(next_act.get_player(&Color::Red).objectives_met, 1);
+        assert_eq!(next_act.get_player(&Color::Blue).objectives_met, 0);
+        assert!(!next_act.is_finished());
+
+        let last_act = campaign::end_act(&setup_act(&next_act));
+        assert_eq!(last_act.get_player(&Color::Blue).power, 6);
+        assert!(last_act.is_finished());
+        let errors = board::setup_game_with_options(&two_player_frontiers(), 0, &SetupOptions { campaign: Some(last_act), ..SetupOptions::default() }).unwrap_err();
+        assert_eq!(errors, vec![SetupError::FinishedCampaign { act: 3, acts: 2 }]);
+    }
+}
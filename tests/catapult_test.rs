@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod test {
+    use correspondence_arcs::data::setup_cards::{SetupCard, two_player_frontiers};
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, Color, GameState, TurnState};
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+    use correspondence_arcs::data::violations::RuleViolation;
+
+    fn catapult(game_state: &GameState, origin_system: u8, destination_systems: Vec<(u8,u8,u8)>) -> Result<GameState, RuleViolation> {
+        let mut game_state = game_state.clone();
+        game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
+
+        actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: BasicAction::Catapult { origin_system: origin_system, destination_systems: destination_systems } }
+        ])
+    }
+
+    #[test]
+    fn catapult_uses_one_pip() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        let new_game_state = catapult(&game_state, 17, vec![(3, 1, 0)]).unwrap();
+
+        assert_eq!(new_game_state.turn_state, TurnState::Actions { action_type: ActionType::Mobilization, pips_left: 3 });
+        assert_eq!(new_game_state.systems()[17].get_all_ships(&Color::Red), 2);
+        assert_eq!(new_game_state.systems()[3].get_all_ships(&Color::Red), 1);
+    }
+
+    #[test]
+    fn catapult_through_gates() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        // Gate 3 is uncontrolled, gate 2 is controlled by red and gate 1 is empty
+        let new_game_state = catapult(&game_state, 17, vec![(1, 1, 0), (2, 1, 0), (12, 1, 0)]).unwrap();
+
+        assert_eq!(new_game_state.systems()[17].get_all_ships(&Color::Red), 0);
+        assert_eq!(new_game_state.systems()[1].get_all_ships(&Color::Red), 1);
+        assert_eq!(new_game_state.systems()[2].get_all_ships(&Color::Red), 3);
+        assert_eq!(new_game_state.systems()[12].get_all_ships(&Color::Red), 1);
+        assert_eq!(new_game_state.systems()[1].update_control(), new_game_state.systems()[1]);
+    }
+
+    #[test]
+    fn catapult_into_rival_controlled_gate() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        let new_game_state = catapult(&game_state, 17, vec![(4, 3, 0)]).unwrap();
+
+        assert_eq!(new_game_state.systems()[4].get_all_ships(&Color::Red), 3);
+        assert_eq!(new_game_state.systems()[4].get_all_ships(&Color::Blue), 2);
+    }
+
+    #[test]
+    fn catapult_through_rival_controlled_gate() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(catapult(&game_state, 17, vec![(20, 1, 0)]), Err(RuleViolation::Unreachable { origin: 17, destination: 20 }));
+    }
+
+    #[test]
+    fn catapult_into_cluster_out_of_play() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(catapult(&game_state, 17, vec![(7, 1, 0)]), Err(RuleViolation::Unreachable { origin: 17, destination: 7 }));
+    }
+
+    #[test]
+    fn catapult_into_origin() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(catapult(&game_state, 17, vec![(17, 1, 0)]), Err(RuleViolation::Unreachable { origin: 17, destination: 17 }));
+    }
+
+    #[test]
+    fn catapult_from_rival_controlled_gate() {
+        let test_setup: SetupCard = SetupCard { players: 2, cluster_out_of_play: vec![0,5], a_locations: vec![20,12], b_locations: vec![17, 18], c_locations: vec![2,3,14,15]};
+        let game_state = board::setup_game(&test_setup).unwrap();
+
+        // Blue controls gate 3, so the red ships can only enter it
+        assert_eq!(catapult(&game_state, 17, vec![(16, 1, 0)]), Err(RuleViolation::Unreachable { origin: 17, destination: 16 }));
+    }
+
+    #[test]
+    fn catapult_into_two_planets() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(catapult(&game_state, 17, vec![(16, 1, 0), (15, 1, 0)]), Err(RuleViolation::MoreThanOnePlanet { destinations: vec![16, 15] }));
+    }
+
+    #[test]
+    fn catapult_more_ships_than_fit_in_a_count() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(catapult(&game_state, 17, vec![(3, 200, 100), (3, 100, 200)]), Err(RuleViolation::NotEnoughShips { system_id: 17, fresh: 255, damaged: 255, fresh_present: 3, damaged_present: 0 }));
+    }
+
+    #[test]
+    fn catapult_without_starport() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(catapult(&game_state, 20, vec![(4, 1, 0)]), Err(RuleViolation::NoLoyalStarport { system_id: 20 }));
+    }
+
+    #[test]
+    fn catapult_too_many_ships() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(catapult(&game_state, 17, vec![(3, 2, 0), (2, 2, 0)]), Err(RuleViolation::NotEnoughShips { system_id: 17, fresh: 4, damaged: 0, fresh_present: 3, damaged_present: 0 }));
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod test{
+    use std::collections::HashMap;
+
+    use correspondence_arcs::data::setup_cards::two_player_frontiers;
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, Color, GameState, ResourceType};
+    use correspondence_arcs::data::system::{BuildingSlot, BuildingType, ControlReason, Presence, Ships, System, SystemType};
+
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+
+    fn test_system(red: Ships, blue: Ships, building_slots: Vec<BuildingSlot>) -> System {
+        System::Used {
+            system_id: 17,
+            system_type: SystemType::Planet { resource: ResourceType::Material },
+            building_slots: building_slots,
+            ships: [(Color::Red, red), (Color::Blue, blue)].iter().cloned().collect::<HashMap<Color, Ships>>(),
+            controlled_by: None,
+            connects_to: vec![3,16]
+        }
+    }
+
+    #[test]
+    fn test_control_report_after_setup(){
+        let game_state: GameState = board::setup_game(&two_player_frontiers()).unwrap();
+
+        let report = game_state.control_report(20).unwrap();
+        assert_eq!(report.controlled_by, Some(Color::Red));
+        assert_eq!(report.reason, ControlReason::MostFreshShips { fresh_ships: 3, runner_up: 0 });
+        assert_eq!(report.presence[0], Presence { player: Color::Red, fresh_ships: 3, damaged_ships: 0, fresh_buildings: 1, damaged_buildings: 0 });
+        assert_eq!(game_state.systems()[20].control_report().unwrap().controlled_by, game_state.systems()[20].update_control().control_report().unwrap().controlled_by);
+
+        assert_eq!(game_state.control_report(0), None);
+    }
+
+    #[test]
+    fn test_buildings_give_presence_without_control(){
+        let game_state: GameState = board::setup_game(&two_player_frontiers()).unwrap();
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 3, 0).unwrap();
+
+        let report = game_state.control_report(17).unwrap();
+        assert_eq!(report.controlled_by, None);
+        assert_eq!(report.reason, ControlReason::NoFreshShips);
+        assert!(game_state.systems()[17].has_presence(&Color::Red));
+        assert!(!game_state.systems()[17].has_presence(&Color::Blue));
+        match &game_state.systems()[17] {
+            System::Used { controlled_by, .. } => assert_eq!(controlled_by, &None),
+            _ => panic!("Expected Used system variant")
+        }
+    }
+
+    #[test]
+    fn test_damaged_ships_give_presence_without_control(){
+        let system = test_system(Ships { fresh: 0, damaged: 3 }, Ships { fresh: 1, damaged: 0 }, vec![BuildingSlot::Empty]);
+
+        let report = system.control_report().unwrap();
+        assert_eq!(report.controlled_by, Some(Color::Blue));
+        assert_eq!(report.reason, ControlReason::MostFreshShips { fresh_ships: 1, runner_up: 0 });
+        assert!(system.has_presence(&Color::Red));
+        assert!(system.has_presence(&Color::Blue));
+    }
+
+    #[test]
+    fn test_tied_fresh_ships_leave_system_uncontrolled(){
+        let system = test_system(
+            Ships { fresh: 2, damaged: 0 },
+            Ships { fresh: 2, damaged: 1 },
+            vec![BuildingSlot::Occupied { fresh: false, player: Color::Red, building_type: BuildingType::Starport, used: false }]
+        );
+
+        let report = system.control_report().unwrap();
+        assert_eq!(report.controlled_by, None);
+        match report.reason {
+            ControlReason::Tied { fresh_ships, players } => {
+                assert_eq!(fresh_ships, 2);
+                assert_eq!(players, vec![Color::Red, Color::Blue]);
+            }
+            reason => panic!("Expected a tie, got {:?}", reason)
+        }
+        assert_eq!(report.presence.iter().map(|p| p.player.clone()).collect::<Vec<Color>>(), vec![Color::Red, Color::Blue]);
+        let red = report.presence.iter().find(|p| p.player == Color::Red).unwrap();
+        assert_eq!(red.damaged_buildings, 1);
+        match system.update_control() {
+            System::Used { controlled_by, .. } => assert_eq!(controlled_by, None),
+            _ => panic!("Expected Used system variant")
+        }
+    }
+
+    #[test]
+    fn test_set_system_recomputes_control(){
+        let mut game_state: GameState = board::setup_game(&two_player_frontiers()).unwrap();
+        assert!(game_state.stale_control().is_empty());
+
+        let system = test_system(Ships { fresh: 1, damaged: 0 }, Ships { fresh: 2, damaged: 0 }, vec![BuildingSlot::Empty]);
+        game_state.set_system(17, system);
+
+        match &game_state.systems()[17] {
+            System::Used { controlled_by, .. } => assert_eq!(controlled_by, &Some(Color::Blue)),
+            _ => panic!("Expected Used system variant")
+        }
+        assert!(game_state.stale_control().is_empty());
+    }
+
+    #[test]
+    fn test_control_stays_current_after_actions(){
+        let mut game_state: GameState = board::setup_game(&two_player_frontiers()).unwrap();
+        game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Warlord) }]);
+
+        let game_state = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Warlord) }, declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: BasicAction::Move { origin_id: 17, destination_id: 16, fresh_ships: 3, damaged_ships: 0 } }
+        ]).unwrap();
+
+        assert!(game_state.stale_control().is_empty());
+        assert_eq!(game_state.control_report(16).unwrap().controlled_by, Some(Color::Red));
+    }
+
+    #[test]
+    fn test_update_systems_keeps_control_fresh(){
+        let mut game_state: GameState = board::setup_game(&two_player_frontiers()).unwrap();
+        game_state.update_systems(|s| match s {
+            System::Used { system_id: 17, .. } => test_system(Ships { fresh: 1, damaged: 0 }, Ships { fresh: 2, damaged: 0 }, vec![BuildingSlot::Empty]),
+            _ => s.clone()
+        });
+
+        assert_eq!(game_state.control_report(17).unwrap().controlled_by, Some(Color::Blue));
+        assert!(game_state.stale_control().is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod test {
+    use correspondence_arcs::data::court_cards::{create_court_deck, min_players};
+    use correspondence_arcs::data::events::GameEvent;
+    use correspondence_arcs::data::setup_cards::two_player_frontiers;
+    use correspondence_arcs::data::game_state::Color;
+    use correspondence_arcs::data::rng::GameRng;
+
+    use correspondence_arcs::board::setup_game_with_set_seed;
+
+    #[test]
+    fn court_deck_follows_player_count() {
+        let two_players = create_court_deck(vec![Color::Red, Color::Blue], &GameRng::new(0)).0;
+        let three_players = create_court_deck(vec![Color::Red, Color::Blue, Color::White], &GameRng::new(0)).0;
+        let four_players = create_court_deck(vec![Color::Red, Color::Blue, Color::White, Color::Yellow], &GameRng::new(0)).0;
+
+        assert_eq!(two_players.len(), 18);
+        assert_eq!(three_players.len(), 22);
+        assert_eq!(four_players.len(), 26);
+
+        assert!(two_players.iter().all(|c| min_players(&c.title()) <= 2));
+        assert!(three_players.iter().all(|c| min_players(&c.title()) <= 3));
+        assert!(four_players.iter().any(|c| c.title() == "Galactic Bards"));
+    }
+
+    #[test]
+    fn starting_court_size() {
+        let game_state = setup_game_with_set_seed(&two_player_frontiers(), 3).unwrap();
+
+        assert_eq!(game_state.court.len(), 3);
+        assert_eq!(game_state.court_draw_pile.len(), 15);
+        assert_eq!(game_state.events, vec![]);
+    }
+
+    #[test]
+    fn reshuffle_discard_pile_into_draw_pile() {
+        let mut game_state = setup_game_with_set_seed(&two_player_frontiers(), 3).unwrap();
+        game_state.court_discard_pile = game_state.court_draw_pile.clone();
+        game_state.court_draw_pile = vec![];
+
+        let new_game_state = game_state.discard_court_card(0).redraw_court_cards();
+
+        assert_eq!(new_game_state.court.len(), 3);
+        assert_eq!(new_game_state.court_draw_pile.len(), 15);
+        assert_eq!(new_game_state.court_discard_pile, vec![]);
+        assert_eq!(new_game_state.events, vec![GameEvent::CourtDiscardReshuffled { cards: 16 }]);
+        assert_eq!(new_game_state, game_state.discard_court_card(0).redraw_court_cards());
+        assert_ne!(new_game_state.rng, game_state.rng);
+    }
+
+    #[test]
+    fn court_cannot_be_refilled() {
+        let mut game_state = setup_game_with_set_seed(&two_player_frontiers(), 3).unwrap();
+        game_state.court_draw_pile = vec![];
+
+        let mut new_game_state = game_state.clone();
+        new_game_state.court.remove(0);
+        let new_game_state = new_game_state.redraw_court_cards();
+
+        assert_eq!(new_game_state.court.len(), 2);
+        assert_eq!(new_game_state.events, vec![GameEvent::CourtNotRefilled { court_size: 2, missing: 1 }]);
+    }
+}
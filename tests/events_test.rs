@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::two_player_frontiers;
+
+    use correspondence_arcs::data::events::{CardPlay, GameEvent};
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, Color, Dice, GameState, ResourceType, TurnState};
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+
+    fn card(action_type: ActionType) -> ActionCard {
+        ActionCard { action_type: action_type, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }
+    }
+
+    fn setup_with_card(action_type: ActionType) -> GameState {
+        let mut game_state = board::setup_game_with_set_seed(&two_player_frontiers(), 1).unwrap();
+        game_state.add_action_cards(&Color::Red, vec![card(action_type)]);
+        game_state
+    }
+
+    #[test]
+    fn test_move_is_logged(){
+        let game_state = setup_with_card(ActionType::Agression);
+        let lead = Action::PlayLeadCard { card: card(ActionType::Agression), declare: None };
+        let move_action = Action::MainAction { basic_action: BasicAction::Move { origin_id: 17, destination_id: 3, fresh_ships: 3, damaged_ships: 0 } };
+
+        let new_game_state = actions::execute_actions(&game_state, vec![lead.clone(), Action::EndPrelude, move_action.clone()]).unwrap();
+
+        assert_eq!(new_game_state.events, vec![
+            GameEvent::ActionTaken { player: Color::Red, action: lead },
+            GameEvent::CardPlayed { player: Color::Red, card: card(ActionType::Agression), play: CardPlay::Lead, seize: false },
+            GameEvent::ActionTaken { player: Color::Red, action: Action::EndPrelude },
+            GameEvent::ActionTaken { player: Color::Red, action: move_action },
+            GameEvent::ShipsMoved { player: Color::Red, origin: 17, destination: 3, fresh: 3, damaged: 0 },
+            GameEvent::ControlChanged { system_id: 3, before: None, after: Some(Color::Red) },
+            GameEvent::ControlChanged { system_id: 17, before: Some(Color::Red), after: None }
+        ]);
+    }
+
+    #[test]
+    fn test_dice_faces_are_logged(){
+        let game_state = setup_with_card(ActionType::Agression);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 3, 0).unwrap();
+        let game_state = actions::moving::move_ships(&game_state, 16, 15, 3, 0).unwrap();
+
+        let new_game_state = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Agression), declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: BasicAction::Battle { target_system: 15, target_player: Color::Blue, dice: vec![Dice::Skirmish, Dice::Skirmish, Dice::Assault] } }
+        ]).unwrap();
+
+        let faces = match new_game_state.events.last().unwrap() {
+            GameEvent::DiceRolled { player, target_system, target_player, faces } => {
+                assert_eq!((player, *target_system, target_player), (&Color::Red, 15, &Color::Blue));
+                faces.clone()
+            },
+            e => panic!("Expected DiceRolled, got {:?}", e)
+        };
+        assert_eq!(faces.iter().map(|(d, _)| d.clone()).collect::<Vec<Dice>>(), vec![Dice::Skirmish, Dice::Skirmish, Dice::Assault]);
+        match new_game_state.turn_state {
+            TurnState::AllocateDiceResults { hits, .. } => assert_eq!(hits, faces.iter().map(|(_, f)| f.hits).sum::<u8>()),
+            t => panic!("Expected AllocateDiceResults, got {:?}", t)
+        }
+    }
+
+    #[test]
+    fn test_gained_resource_is_logged(){
+        let game_state = setup_with_card(ActionType::Administration);
+
+        let new_game_state = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Administration), declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: BasicAction::Tax { target_system: 20, target_player: Color::Red } },
+            Action::AllocateResources { configuration: vec![(0, ResourceType::Psionics), (1, ResourceType::Material), (2, ResourceType::Psionics)] }
+        ]).unwrap();
+
+        assert_eq!(new_game_state.events.last(), Some(&GameEvent::ResourceChanged { player: Color::Red, resource: ResourceType::Psionics, change: 1 }));
+    }
+
+    #[test]
+    fn test_round_and_chapter_end_are_logged(){
+        let mut game_state = setup_with_card(ActionType::Construction);
+        game_state.add_action_cards(&Color::Blue, vec![card(ActionType::Agression)]);
+
+        let new_game_state = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Construction), declare: Some(AmbitionTypes::Tycoon) },
+            Action::EndPrelude,
+            Action::EndTurn,
+            Action::Copy { card: card(ActionType::Agression), seize: None },
+            Action::EndPrelude,
+            Action::EndTurn
+        ]).unwrap();
+
+        let ending: Vec<GameEvent> = new_game_state.events.iter().filter(|e| matches!(e, GameEvent::AmbitionDeclared { .. } | GameEvent::RoundEnded { .. } | GameEvent::AmbitionScored { .. } | GameEvent::ChapterEnded { .. })).cloned().collect();
+        assert_eq!(ending, vec![
+            GameEvent::AmbitionDeclared { player: Color::Red, ambition: AmbitionTypes::Tycoon },
+            GameEvent::RoundEnded { initiative: Color::Red },
+            GameEvent::AmbitionScored { ambition: AmbitionTypes::Tycoon, points: vec![(Color::Red, 3), (Color::Blue, 3)] },
+            GameEvent::ChapterEnded { chapter: 1 }
+        ]);
+        assert_eq!(new_game_state.chapter, 2);
+    }
+
+    #[test]
+    fn test_game_ends_after_the_last_chapter(){
+        let mut game_state = setup_with_card(ActionType::Construction);
+        game_state.add_action_cards(&Color::Blue, vec![card(ActionType::Agression)]);
+        game_state.chapter = game_state.rules.chapters;
+        game_state.players.get_mut(&Color::Red).unwrap().power = 2;
+
+        let new_game_state = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Construction), declare: Some(AmbitionTypes::Tycoon) },
+            Action::EndPrelude,
+            Action::EndTurn,
+            Action::Copy { card: card(ActionType::Agression), seize: None },
+            Action::EndPrelude,
+            Action::EndTurn
+        ]).unwrap();
+
+        assert_eq!(new_game_state.turn_state, TurnState::GameOver);
+        assert_eq!(new_game_state.events.last(), Some(&GameEvent::GameEnded { winners: vec![Color::Red] }));
+        assert!(actions::legal_actions(&new_game_state).actions.is_empty());
+        assert!(actions::execute_action(&new_game_state, Action::EndTurn).is_err());
+    }
+
+    #[test]
+    fn test_game_does_not_end_early(){
+        let mut game_state = setup_with_card(ActionType::Construction);
+        game_state.add_action_cards(&Color::Blue, vec![card(ActionType::Agression)]);
+
+        let new_game_state = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Construction), declare: Some(AmbitionTypes::Tycoon) },
+            Action::EndPrelude,
+            Action::EndTurn,
+            Action::Copy { card: card(ActionType::Agression), seize: None },
+            Action::EndPrelude,
+            Action::EndTurn
+        ]).unwrap();
+
+        assert_eq!(new_game_state.turn_state, TurnState::TrickTaking);
+        assert!(!new_game_state.events.iter().any(|e| matches!(e, GameEvent::GameEnded { .. })));
+    }
+
+    #[test]
+    fn test_log_is_only_appended(){
+        let game_state = setup_with_card(ActionType::Agression);
+        let g1 = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Agression), declare: None },
+            Action::EndPrelude
+        ]).unwrap();
+
+        for action in actions::legal_actions(&g1).iter() {
+            let g2 = actions::execute_action(&g1, action.clone()).unwrap();
+            assert!(g2.events.starts_with(&g1.events), "{:?} rewrote the log", action);
+        }
+    }
+}
@@ -9,7 +9,7 @@ mod test{
 
     #[test]
     fn influence_once_then_twice() {
-        let mut game_state = board::setup_game(&two_player_frontiers());
+        let mut game_state = board::setup_game(&two_player_frontiers()).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![
             ActionCard { action_type: ActionType::Administration, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }
@@ -28,7 +28,7 @@ mod test{
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Influence { card_id: 0 }},
             Action::EndTurn
-        ]);
+        ]).unwrap();
 
         print!("{:?}", game_state.lead_card);
         assert_eq!(g1.court[0].controlled_by(), Some(Color::Red));
@@ -39,13 +39,13 @@ mod test{
             Action::Surpass { card: ActionCard { action_type: ActionType::Administration, number: 6, pips: 2, declared_ambition: Some(AmbitionTypes::Empath) }, seize: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Influence { card_id: 0 }}
-        ]); 
+        ]).unwrap(); 
 
         assert_eq!(g2.court[0].controlled_by(), None);
         assert_eq!(g2.players.get(&Color::Red).unwrap().reserve.get(&ReserveType::Agents).unwrap(), &9);
         assert_eq!(g2.players.get(&Color::Blue).unwrap().reserve.get(&ReserveType::Agents).unwrap(), &9);
 
-        let g3 = execute_action(&g2, Action::MainAction {basic_action: BasicAction::Influence { card_id: 0 }});
+        let g3 = execute_action(&g2, Action::MainAction {basic_action: BasicAction::Influence { card_id: 0 }}).unwrap();
 
         assert_eq!(g3.players.get(&Color::Red).unwrap().reserve.get(&ReserveType::Agents).unwrap(), &9);
         assert_eq!(g3.players.get(&Color::Blue).unwrap().reserve.get(&ReserveType::Agents).unwrap(), &8);
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::two_player_frontiers;
+
+    use correspondence_arcs::data::court_cards::Guild;
+    use correspondence_arcs::data::game_state::{Color, GameState, PlayerArea, RaidTarget, ResourceSlot, ResourceType};
+    use correspondence_arcs::board;
+
+    fn red_area_with_guild_card() -> PlayerArea {
+        let game_state: GameState = board::setup_game(&two_player_frontiers()).unwrap();
+        let mut player_area = game_state.get_player_area(&Color::Red);
+        player_area.guild_cards.push(Guild { title: "Loyal Engineers".to_string(), description: "".to_string(), resource: ResourceType::Material, keys: 3, prelude_action: None });
+        player_area
+    }
+
+    #[test]
+    fn test_raid_cost(){
+        let player_area = red_area_with_guild_card();
+
+        assert_eq!(player_area.raid_cost(&RaidTarget::Resource { resource_id: 0 }), Some(3));
+        assert_eq!(player_area.raid_cost(&RaidTarget::Resource { resource_id: 1 }), Some(1));
+        assert_eq!(player_area.raid_cost(&RaidTarget::Resource { resource_id: 2 }), None);
+        assert_eq!(player_area.raid_cost(&RaidTarget::Resource { resource_id: 3 }), None);
+        assert_eq!(player_area.raid_cost(&RaidTarget::GuildCard { card_id: 0 }), Some(3));
+        assert_eq!(player_area.raid_cost(&RaidTarget::GuildCard { card_id: 1 }), None);
+
+        assert_eq!(player_area.total_raid_cost(&[RaidTarget::Resource { resource_id: 1 }, RaidTarget::GuildCard { card_id: 0 }]), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot raid")]
+    fn test_raid_empty_slot(){
+        red_area_with_guild_card().total_raid_cost(&[RaidTarget::Resource { resource_id: 2 }]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot raid the same target twice")]
+    fn test_raid_same_target_twice(){
+        red_area_with_guild_card().total_raid_cost(&[RaidTarget::Resource { resource_id: 0 }, RaidTarget::Resource { resource_id: 0 }]);
+    }
+
+    #[test]
+    fn test_raid_targets_and_resource_keys(){
+        let player_area = red_area_with_guild_card();
+
+        assert_eq!(player_area.raid_targets(), vec![
+            (RaidTarget::Resource { resource_id: 0 }, 3),
+            (RaidTarget::GuildCard { card_id: 0 }, 3),
+            (RaidTarget::Resource { resource_id: 1 }, 1)
+        ]);
+
+        let resource_keys = player_area.resource_keys();
+        assert_eq!(resource_keys.get(&ResourceType::Psionics), Some(&3));
+        assert_eq!(resource_keys.get(&ResourceType::Material), Some(&4));
+        assert_eq!(resource_keys.get(&ResourceType::Fuel), None);
+    }
+
+    #[test]
+    fn test_open_slots_by_keys(){
+        let mut player_area = red_area_with_guild_card();
+        assert_eq!(player_area.open_slots_by_keys(), vec![0, 1, 2]);
+
+        player_area.resource_slots[3] = ResourceSlot::Unused { keys: 2 };
+        assert_eq!(player_area.open_slots_by_keys(), vec![0, 3, 1, 2]);
+    }
+}
@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::{two_player_frontiers, SetupError, SetupOptions};
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, BuildType, Color, GameState, RaidTarget, ReserveType, ResourceSlot, ResourceType, TurnState};
+    use correspondence_arcs::data::leaders::{create_leaders, create_lore_deck, Draft};
+    use correspondence_arcs::data::system::{BuildingSlot, BuildingType, System};
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+    use correspondence_arcs::data::violations::RuleViolation;
+    use correspondence_arcs::data::rng::GameRng;
+
+    fn open_draft() -> Draft {
+        Draft { players: vec![Color::Red, Color::Blue], leaders: create_leaders(), lore: create_lore_deck(), picks: vec![] }
+    }
+
+    fn setup_with_picks(blue: (&str, &str), red: (&str, &str)) -> GameState {
+        let draft = open_draft().pick(blue.0, blue.1).pick(red.0, red.1);
+        board::setup_game_with_options(&two_player_frontiers(), 0, &SetupOptions { leaders_and_lore: Some(draft), ..SetupOptions::default() }).unwrap()
+    }
+
+    #[test]
+    fn test_draft(){
+        let draft = Draft::deal(vec![Color::Red, Color::Blue, Color::White], &GameRng::new(3)).0;
+        assert_eq!(draft.leaders.len(), 4);
+        assert_eq!(draft.lore.len(), 4);
+        assert_eq!(draft.next_player(), Some(Color::White));
+
+        let leader = draft.leaders[0].title.clone();
+        let lore = draft.lore[1].title.clone();
+        let draft = draft.pick(&leader, &lore);
+        assert_eq!(draft.next_player(), Some(Color::Blue));
+        assert_eq!(draft.picks_of(&Color::White).map(|(l, r)| (l.title, r.title)), Some((leader.clone(), lore)));
+        assert!(!draft.leaders.iter().any(|l| l.title == leader));
+
+        let draft = draft.pick(&draft.leaders[0].title.clone(), &draft.lore[0].title.clone());
+        let draft = draft.pick(&draft.leaders[0].title.clone(), &draft.lore[0].title.clone());
+        assert!(draft.is_complete());
+        assert_eq!(draft.next_player(), None);
+        assert_eq!(Draft::deal(vec![Color::Red, Color::Blue, Color::White], &GameRng::new(3)).0, Draft::deal(vec![Color::Red, Color::Blue, Color::White], &GameRng::new(3)).0);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not offered")]
+    fn test_pick_leader_twice(){
+        open_draft().pick("Elder", "Mirror Plating").pick("Elder", "Tool Priests");
+    }
+
+    #[test]
+    fn test_setup_with_incomplete_draft(){
+        let draft = open_draft().pick("Elder", "Mirror Plating");
+        let errors = board::setup_game_with_options(&two_player_frontiers(), 0, &SetupOptions { leaders_and_lore: Some(draft), ..SetupOptions::default() }).unwrap_err();
+        assert_eq!(errors, vec![SetupError::IncompleteDraft { players: vec![Color::Red, Color::Blue] }]);
+    }
+
+    #[test]
+    fn test_leaders_change_setup(){
+        let game_state = setup_with_picks(("Elder", "Mirror Plating"), ("Upstart", "Living Structures"));
+
+        let red = game_state.get_player_area(&Color::Red);
+        assert_eq!(red.leader.as_ref().map(|l| l.title.as_str()), Some("Upstart"));
+        assert_eq!(red.lore.iter().map(|l| l.title.as_str()).collect::<Vec<_>>(), vec!["Living Structures"]);
+        assert_eq!(red.resource_slots[0], ResourceSlot::Used { keys: 3, resource: ResourceType::Material });
+        assert_eq!(red.resource_slots[1], ResourceSlot::Used { keys: 1, resource: ResourceType::Fuel });
+
+        // The Upstart starts with a Starport on A and a City on B
+        match (&game_state.systems()[20], &game_state.systems()[17]) {
+            (System::Used { building_slots: a, .. }, System::Used { building_slots: b, .. }) => {
+                assert_eq!(a[0], BuildingSlot::Occupied { fresh: true, player: Color::Red, building_type: BuildingType::Starport, used: false });
+                assert_eq!(b[0], BuildingSlot::Occupied { fresh: true, player: Color::Red, building_type: BuildingType::City, used: false });
+            }
+            _ => panic!("Expected Used system variant")
+        }
+
+        let blue = game_state.get_player_area(&Color::Blue);
+        assert_eq!(blue.resource_slots[0], ResourceSlot::Used { keys: 3, resource: ResourceType::Relics });
+        for player in game_state.players.values() {
+            let ships_on_board: u8 = game_state.systems().iter().map(|s| s.get_all_ships(&player.player)).sum();
+            assert_eq!(ships_on_board + player.reserve.get(&ReserveType::Ships).unwrap(), 15);
+            assert_eq!(player.reserve.get(&ReserveType::Cities), Some(&4));
+            assert_eq!(player.reserve.get(&ReserveType::Starports), Some(&4));
+        }
+    }
+
+    #[test]
+    fn test_extra_keys(){
+        let game_state = setup_with_picks(("Elder", "Mirror Plating"), ("Upstart", "Living Structures"));
+
+        assert_eq!(game_state.get_player_area(&Color::Blue).raid_cost(&RaidTarget::Resource { resource_id: 0 }), Some(5));
+        assert_eq!(game_state.get_player_area(&Color::Red).raid_cost(&RaidTarget::Resource { resource_id: 0 }), Some(3));
+    }
+
+    #[test]
+    fn test_substitute_resource_in_prelude(){
+        let mut game_state = setup_with_picks(("Elder", "Mirror Plating"), ("Fuel-Drinker", "Tool Priests"));
+        game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
+
+        // Fuel pays for building a Ship at the Starport
+        let new_game_state = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
+            Action::PreludeResourceAction { basic_action: BasicAction::Build { target_system: 17, build_type: BuildType::Ship }, used_resource: 0 }
+        ]).unwrap();
+
+        assert_eq!(new_game_state.systems()[17].get_fresh_ships(&Color::Red), 3);
+        assert_eq!(new_game_state.turn_state, TurnState::Prelude { action_type: ActionType::Agression, pips_left: 3 });
+    }
+
+    #[test]
+    fn test_substitute_resource_without_power(){
+        let mut game_state = setup_with_picks(("Fuel-Drinker", "Tool Priests"), ("Elder", "Mirror Plating"));
+        game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
+
+        // The Elder holds Relics and Material, Relics cannot pay for building
+        let result = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
+            Action::PreludeResourceAction { basic_action: BasicAction::Build { target_system: 17, build_type: BuildType::Ship }, used_resource: 0 }
+        ]);
+        assert_eq!(result, Err(RuleViolation::WrongPreludeResource { action: BasicAction::Build { target_system: 17, build_type: BuildType::Ship }, resource: Some(ResourceType::Relics), lead: ActionType::Agression }));
+    }
+
+    #[test]
+    fn test_ship_with_city(){
+        let mut game_state = setup_with_picks(("Elder", "Mirror Plating"), ("Rebel", "Tool Priests"));
+        game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 2, 0).unwrap();
+        let ships_in_reserve = *game_state.get_player_area(&Color::Red).reserve.get(&ReserveType::Ships).unwrap();
+
+        let new_game_state = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Construction, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: BasicAction::Build { target_system: 16, build_type: BuildType::City } }
+        ]).unwrap();
+
+        assert_eq!(new_game_state.systems()[16].get_fresh_ships(&Color::Red), 3);
+        assert_eq!(new_game_state.get_player_area(&Color::Red).reserve.get(&ReserveType::Ships), Some(&(ships_in_reserve - 1)));
+    }
+}
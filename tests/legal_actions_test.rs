@@ -0,0 +1,305 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::two_player_frontiers;
+
+    use itertools::{iproduct, Itertools};
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, BuildType, Color, Dice, GameState, ResourceType, TurnState};
+    use correspondence_arcs::data::legal_actions::{LegalActions, ShipMoves};
+    use correspondence_arcs::data::reach::{Reach, SYSTEMS};
+    use correspondence_arcs::data::system::{Ships, System};
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+
+    fn card(action_type: ActionType, pips: u8) -> ActionCard {
+        ActionCard { action_type: action_type, number: 2, pips: pips, declared_ambition: Some(AmbitionTypes::Tycoon) }
+    }
+
+    fn setup_with_card(action_type: ActionType, pips: u8) -> GameState {
+        let mut game_state = board::setup_game(&two_player_frontiers()).unwrap();
+        game_state.add_action_cards(&Color::Red, vec![card(action_type, pips)]);
+        game_state
+    }
+
+    fn assert_all_executable(game_state: &GameState, legal_actions: &LegalActions) {
+        for action in legal_actions.iter() {
+            assert!(actions::execute_action(game_state, action.clone()).is_ok(), "{:?} should be legal", action);
+        }
+    }
+
+    // Every candidate is contained exactly when execute_action accepts it
+    fn assert_complete(game_state: &GameState, legal_actions: &LegalActions, candidates: Vec<Action>) {
+        for action in candidates {
+            let accepted = actions::execute_action(game_state, action.clone());
+            assert_eq!(legal_actions.contains(&action), accepted.is_ok(), "{:?} is accepted with {:?}", action, accepted);
+        }
+    }
+
+    fn main_action(action_type: ActionType) -> GameState {
+        actions::execute_actions(&setup_with_card(action_type.clone(), 1), vec![
+            Action::PlayLeadCard { card: card(action_type, 1), declare: None },
+            Action::EndPrelude
+        ]).unwrap()
+    }
+
+    // Red holds two fresh ships in each of ten systems without Blue ships
+    fn spread_out(game_state: &GameState) -> GameState {
+        let mut game_state = game_state.clone();
+        let systems: Vec<u8> = game_state.reach.systems().into_iter().filter(|s| !game_state.systems()[*s as usize].has_presence(&Color::Blue)).take(10).collect();
+        game_state.update_systems(|s| match s {
+            System::Used { system_id, system_type, building_slots, ships, controlled_by, connects_to } if systems.contains(system_id) => System::Used {
+                system_id: *system_id,
+                system_type: system_type.clone(),
+                building_slots: building_slots.clone(),
+                ships: ships.iter().map(|(c, s)| (c.clone(), if *c == Color::Red {Ships { fresh: 2, damaged: 0 }} else {s.clone()})).collect(),
+                controlled_by: controlled_by.clone(),
+                connects_to: connects_to.clone()
+            },
+            _ => s.clone()
+        });
+        game_state
+    }
+
+    #[test]
+    fn test_lead_options(){
+        let game_state = setup_with_card(ActionType::Construction, 1);
+
+        let legal_actions = actions::legal_actions(&game_state);
+
+        assert!(legal_actions.ship_moves.is_empty());
+        assert_eq!(legal_actions.actions, vec![
+            Action::PlayLeadCard { card: card(ActionType::Construction, 1), declare: None },
+            Action::PlayLeadCard { card: card(ActionType::Construction, 1), declare: Some(AmbitionTypes::Tycoon) }
+        ]);
+    }
+
+    #[test]
+    fn test_prelude_can_always_be_ended(){
+        let game_state = setup_with_card(ActionType::Construction, 1);
+        let g1 = actions::execute_action(&game_state, Action::PlayLeadCard { card: card(ActionType::Construction, 1), declare: None }).unwrap();
+
+        let legal_actions = actions::legal_actions(&g1);
+
+        assert!(legal_actions.contains(&Action::EndPrelude));
+        assert!(legal_actions.iter().all(|a| matches!(a, Action::EndPrelude | Action::PreludeResourceAction { .. })));
+        assert_all_executable(&g1, &legal_actions);
+    }
+
+    #[test]
+    fn test_main_actions_follow_the_card(){
+        let game_state = setup_with_card(ActionType::Agression, 1);
+        let g1 = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Agression, 1), declare: None },
+            Action::EndPrelude
+        ]).unwrap();
+
+        let legal_actions = actions::legal_actions(&g1);
+
+        assert!(legal_actions.contains(&Action::EndTurn));
+        assert!(legal_actions.contains(&Action::MainAction { basic_action: BasicAction::Move { origin_id: 17, destination_id: 3, fresh_ships: 3, damaged_ships: 0 } }));
+        assert!(!legal_actions.contains(&Action::MainAction { basic_action: BasicAction::Move { origin_id: 17, destination_id: 18, fresh_ships: 1, damaged_ships: 0 } }));
+        assert!(!legal_actions.contains(&Action::MainAction { basic_action: BasicAction::Build { target_system: 17, build_type: BuildType::Ship } }));
+        // no rival shares a system with Red at the start
+        assert!(!legal_actions.iter().any(|a| matches!(a, Action::MainAction { basic_action: BasicAction::Battle { .. } })));
+        assert_all_executable(&g1, &legal_actions);
+    }
+
+    #[test]
+    fn test_battle_dice_up_to_ship_count(){
+        let game_state = setup_with_card(ActionType::Agression, 1);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 2, 0).unwrap();
+        let game_state = actions::moving::move_ships(&game_state, 16, 15, 2, 0).unwrap();
+        let g1 = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Agression, 1), declare: None },
+            Action::EndPrelude
+        ]).unwrap();
+
+        let battles: Vec<Action> = actions::legal_actions(&g1).iter().filter(|a| matches!(a, Action::MainAction { basic_action: BasicAction::Battle { .. } })).collect();
+
+        assert_eq!(battles.len(), 9);
+        assert!(battles.contains(&Action::MainAction { basic_action: BasicAction::Battle { target_system: 15, target_player: Color::Blue, dice: vec![Dice::Skirmish, Dice::Raid] } }));
+        assert!(battles.iter().all(|a| matches!(a, Action::MainAction { basic_action: BasicAction::Battle { target_system: 15, target_player: Color::Blue, .. } })));
+    }
+
+    #[test]
+    fn test_no_main_actions_without_pips(){
+        let game_state = setup_with_card(ActionType::Construction, 1);
+        let g1 = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Construction, 1), declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: BasicAction::Build { target_system: 17, build_type: BuildType::Ship } }
+        ]).unwrap();
+
+        assert_eq!(actions::legal_actions(&g1), LegalActions { actions: vec![Action::EndTurn], ship_moves: vec![] });
+    }
+
+    #[test]
+    fn test_allocation_configurations(){
+        let game_state = setup_with_card(ActionType::Administration, 1);
+        let g1 = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Administration, 1), declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: BasicAction::Tax { target_system: 20, target_player: Color::Red } }
+        ]).unwrap();
+        assert_eq!(g1.turn_state, TurnState::AllocateResources { player: Color::Red, resources: vec![ResourceType::Psionics] });
+
+        let legal_actions = actions::legal_actions(&g1);
+
+        assert_eq!(legal_actions.count(), 19);
+        assert!(legal_actions.contains(&Action::AllocateResources { configuration: vec![(0, ResourceType::Psionics), (1, ResourceType::Material), (2, ResourceType::Psionics)] }));
+        assert!(!legal_actions.contains(&Action::AllocateResources { configuration: vec![(0, ResourceType::Material), (1, ResourceType::Material)] }));
+        assert_all_executable(&g1, &legal_actions);
+    }
+
+    #[test]
+    fn test_spread_out_fleets_are_grouped(){
+        let g1 = spread_out(&main_action(ActionType::Mobilization));
+
+        let legal_actions = actions::legal_actions(&g1);
+
+        // Listing every split of the ships would take more than half a million actions
+        assert!(legal_actions.count() > 500_000);
+        assert!(legal_actions.actions.len() < 1_000);
+        assert!(legal_actions.ship_moves.len() <= 2 * SYSTEMS as usize);
+        // A gate reached by fleets from at least ten origins offers every selection of up to two ships from each
+        let gathering = legal_actions.ship_moves.iter().find(|o| matches!(&o.moves, ShipMoves::Fleets { fleets, .. } if fleets.len() >= 10)).unwrap();
+        assert!(gathering.moves.count() >= 3u64.pow(10) - 1);
+    }
+
+    #[test]
+    fn test_ship_moves_count_their_members(){
+        let g1 = main_action(ActionType::Mobilization);
+
+        let legal_actions = actions::legal_actions(&g1);
+        let listed: Vec<Action> = legal_actions.iter().collect();
+
+        assert_eq!(listed.len() as u64, legal_actions.count());
+        assert_eq!(listed.iter().map(|a| format!("{:?}", a)).unique().count(), listed.len());
+        assert!(listed.iter().all(|a| legal_actions.contains(a)));
+        assert_all_executable(&g1, &legal_actions);
+    }
+
+    #[test]
+    fn test_ship_moves_are_complete(){
+        let g1 = main_action(ActionType::Mobilization);
+        let origins = [2, 14, 17];
+        let systems: Vec<u8> = (0..SYSTEMS).collect();
+        let gates: Vec<u8> = (0..6).collect();
+
+        let moves = iproduct!(origins.iter().chain(&[3]), systems.iter(), [(1, 0), (2, 0), (0, 1)])
+            .map(|(o, d, (f, dmg))| BasicAction::Move { origin_id: *o, destination_id: *d, fresh_ships: f, damaged_ships: dmg });
+        let paths: Vec<Vec<u8>> = origins.iter()
+            .flat_map(|o| vec![vec![*o]].into_iter().chain(gates.iter().map(move |g| vec![*o, *g])).chain(iproduct!(gates.iter(), gates.iter()).map(move |(g, h)| vec![*o, *g, *h])))
+            .collect();
+        let single_fleets = iproduct!(systems.iter(), paths.iter())
+            .map(|(d, path)| BasicAction::MoveFleets { destination_id: *d, fleets: vec![(path.clone(), 1, 0)] });
+        let short_paths: Vec<Vec<u8>> = origins.iter().flat_map(|o| vec![vec![*o], vec![*o, Reach::gate_of(*o)]]).collect();
+        // Pairs in either order, also from the same origin
+        let fleet_pairs = iproduct!(systems.iter(), short_paths.iter(), short_paths.iter(), [(1, 0), (2, 0)])
+            .map(|(d, a, b, (f, dmg))| BasicAction::MoveFleets { destination_id: *d, fleets: vec![(a.clone(), f, dmg), (b.clone(), 1, 0)] });
+        let catapults = systems.iter().map(|s| vec![(*s, 1, 0)])
+            .chain(iproduct!(systems.iter(), systems.iter()).map(|(a, b)| vec![(*a, 1, 0), (*b, 1, 0)]))
+            .map(|destinations| BasicAction::Catapult { origin_system: 17, destination_systems: destinations });
+
+        let candidates = moves.chain(single_fleets).chain(fleet_pairs).chain(catapults).map(|b| Action::MainAction { basic_action: b }).collect();
+        assert_complete(&g1, &actions::legal_actions(&g1), candidates);
+    }
+
+    #[test]
+    fn test_fleets_take_any_passable_path(){
+        let mut g1 = main_action(ActionType::Mobilization);
+        // Blue leaves gate 4, so it becomes passable for Red
+        g1.update_systems(|s| match s {
+            System::Used { system_id: 4, system_type, building_slots, ships, controlled_by, connects_to } => System::Used {
+                system_id: 4,
+                system_type: system_type.clone(),
+                building_slots: building_slots.clone(),
+                ships: ships.keys().map(|c| (c.clone(), Ships { fresh: 0, damaged: 0 })).collect(),
+                controlled_by: controlled_by.clone(),
+                connects_to: connects_to.clone()
+            },
+            _ => s.clone()
+        });
+        let legal_actions = actions::legal_actions(&g1);
+
+        // Gates 2 and 4 both lead from gate 3 to gate 1, as the ring skips the clusters out of play
+        for path in [vec![17, 3, 2], vec![17, 3, 4]] {
+            let action = Action::MainAction { basic_action: BasicAction::MoveFleets { destination_id: 1, fleets: vec![(path, 1, 0)] } };
+            assert!(actions::execute_action(&g1, action.clone()).is_ok());
+            assert!(legal_actions.contains(&action), "{:?} should be legal", action);
+        }
+    }
+
+    #[test]
+    fn test_fleets_from_the_same_origin(){
+        let g1 = main_action(ActionType::Mobilization);
+        let legal_actions = actions::legal_actions(&g1);
+        let fleets = |count: usize| Action::MainAction { basic_action: BasicAction::MoveFleets { destination_id: 13, fleets: vec![(vec![17, 3, 2], 1, 0); count] } };
+
+        for count in [2, 3] {
+            assert!(actions::execute_action(&g1, fleets(count)).is_ok());
+            assert!(legal_actions.contains(&fleets(count)));
+        }
+        assert!(actions::execute_action(&g1, fleets(4)).is_err());
+        assert!(!legal_actions.contains(&fleets(4)));
+    }
+
+    #[test]
+    fn test_follow_options_are_complete(){
+        let mut game_state = setup_with_card(ActionType::Construction, 3);
+        game_state.add_action_cards(&Color::Red, vec![card(ActionType::Agression, 4)]);
+        game_state.add_action_cards(&Color::Blue, vec![card(ActionType::Construction, 5), card(ActionType::Agression, 2), card(ActionType::Mobilization, 6)]);
+        let g1 = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Construction, 3), declare: None },
+            Action::EndPrelude,
+            Action::EndTurn
+        ]).unwrap();
+        let cards: Vec<ActionCard> = [Color::Red, Color::Blue].iter().flat_map(|c| g1.get_player_area(c).action_cards).collect();
+
+        let candidates = iproduct!(cards.iter(), vec![None].into_iter().chain(cards.iter().map(Some)))
+            .flat_map(|(card, seize)| vec![
+                Action::Surpass { card: card.clone(), seize: seize.cloned() },
+                Action::Copy { card: card.clone(), seize: seize.cloned() },
+                Action::Pivot { card: card.clone(), seize: seize.cloned() }
+            ])
+            .collect();
+        assert_complete(&g1, &actions::legal_actions(&g1), candidates);
+    }
+
+    #[test]
+    fn test_allocations_are_complete(){
+        let game_state = setup_with_card(ActionType::Administration, 1);
+        let g1 = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Administration, 1), declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: BasicAction::Tax { target_system: 20, target_player: Color::Red } }
+        ]).unwrap();
+        let legal_actions = actions::legal_actions(&g1);
+
+        let candidates = legal_actions.actions.iter()
+            .flat_map(|a| match a {
+                Action::AllocateResources { configuration } => configuration.iter().cloned().permutations(configuration.len()).map(|c| Action::AllocateResources { configuration: c }).collect(),
+                _ => vec![]
+            })
+            .collect();
+        assert_complete(&g1, &legal_actions, candidates);
+    }
+
+    #[test]
+    fn test_battle_dice_in_any_order(){
+        let game_state = setup_with_card(ActionType::Agression, 1);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 2, 0).unwrap();
+        let game_state = actions::moving::move_ships(&game_state, 16, 15, 2, 0).unwrap();
+        let g1 = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Agression, 1), declare: None },
+            Action::EndPrelude
+        ]).unwrap();
+        let dice = [Dice::Skirmish, Dice::Assault, Dice::Raid];
+
+        let candidates = (1..=3)
+            .flat_map(|n| (0..n).map(|_| dice.iter().cloned()).multi_cartesian_product())
+            .map(|d| Action::MainAction { basic_action: BasicAction::Battle { target_system: 15, target_player: Color::Blue, dice: d } })
+            .collect();
+        assert_complete(&g1, &actions::legal_actions(&g1), candidates);
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod test{
+    use serde_json::Value;
+
+    use correspondence_arcs::data::game_state::{Action, ActionType, Color, ReserveType, Trophy, TurnState};
+    use correspondence_arcs::save::{migrate, RecordFile, SaveError, SaveFile, SAVE_VERSION};
+    use correspondence_arcs::actions;
+
+    // Saved by version 1 after Red led an Agression card and moved two ships, with a Blue trophy in Red's area
+    const SAVE_V1: &str = include_str!("fixtures/save_v1.json");
+    // Bare record written by version 1, the first player led a card and ended the turn without actions
+    const RECORD_V1: &str = include_str!("fixtures/record_v1.json");
+
+    #[test]
+    fn test_load_version_1(){
+        let loaded = SaveFile::from_json(SAVE_V1).unwrap();
+
+        assert_eq!(loaded.version, SAVE_VERSION);
+        assert_eq!(loaded.game_state.get_player_area(&Color::Red).trophies, vec![Trophy { trophy_type: ReserveType::Ships, count: 2, player: Color::Blue }]);
+        assert_eq!(loaded.game_state.turn_state, TurnState::Actions { action_type: ActionType::Agression, pips_left: 2 });
+    }
+
+    #[test]
+    fn test_migrated_game_continues(){
+        let game_state = SaveFile::from_json(SAVE_V1).unwrap().game_state;
+
+        let game_state = actions::execute_action(&game_state, Action::EndTurn).unwrap();
+
+        let json = SaveFile::new(&game_state).to_json();
+        assert_eq!(SaveFile::from_json(&json).unwrap().game_state, game_state);
+    }
+
+    #[test]
+    fn test_migrate_renames_trophies(){
+        let value: Value = serde_json::from_str(SAVE_V1).unwrap();
+
+        let migrated = migrate(value).unwrap();
+
+        assert_eq!(migrated["version"], SAVE_VERSION);
+        let red = &migrated["game_state"]["players"]["Red"];
+        assert!(red.get("tropies").is_none());
+        assert_eq!(red["trophies"][0]["count"], 2);
+    }
+
+    #[test]
+    fn test_current_version_is_unchanged(){
+        let value: Value = serde_json::from_str(SAVE_V1).unwrap();
+        let current = migrate(value).unwrap();
+
+        assert_eq!(migrate(current.clone()).unwrap(), current);
+    }
+
+    #[test]
+    fn test_unknown_versions(){
+        let mut value: Value = serde_json::from_str(SAVE_V1).unwrap();
+        value["version"] = Value::from(0);
+
+        assert_eq!(migrate(value), Err(SaveError::UnsupportedVersion { found: 0, supported: SAVE_VERSION }));
+    }
+
+    #[test]
+    fn test_load_record_version_1(){
+        let loaded = RecordFile::from_json(RECORD_V1).unwrap();
+
+        assert_eq!(loaded.version, SAVE_VERSION);
+        assert_eq!(loaded.record.seed, 12);
+        assert_eq!(loaded.record.actions.iter().map(|r| r.action.clone()).skip(1).collect::<Vec<_>>(), vec![Action::EndPrelude, Action::EndTurn]);
+        // The trophies were renamed, so the states have other digests now
+        let value: Value = serde_json::from_str(RECORD_V1).unwrap();
+        assert_ne!(value["initial_digest"], loaded.record.initial_digest);
+        assert_eq!(loaded.record.replay().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_record_which_does_not_replay(){
+        let mut value: Value = serde_json::from_str(RECORD_V1).unwrap();
+        value["actions"][1]["events"] = Value::Array(vec![]);
+
+        assert!(matches!(RecordFile::from_json(&value.to_string()), Err(SaveError::Diverged { .. })));
+    }
+
+    #[test]
+    fn test_record_round_trip(){
+        let record = RecordFile::from_json(RECORD_V1).unwrap();
+
+        let json = record.to_json();
+
+        assert!(json.starts_with("{\"version\":"));
+        assert_eq!(RecordFile::from_json(&json).unwrap(), record);
+    }
+
+    #[test]
+    fn test_unknown_record_versions(){
+        let json = RecordFile::from_json(RECORD_V1).unwrap().to_json().replacen(&format!("\"version\":{}", SAVE_VERSION), "\"version\":9", 1);
+
+        assert_eq!(RecordFile::from_json(&json), Err(SaveError::UnsupportedVersion { found: 9, supported: SAVE_VERSION }));
+    }
+}
@@ -2,15 +2,16 @@
 mod test {
     use correspondence_arcs::data::setup_cards::{SetupCard, two_player_frontiers};
 
-    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, Color, GameState};
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, Color, GameState, TurnState};
     use correspondence_arcs::data::system::{Ships, System};
     use correspondence_arcs::board;
     use correspondence_arcs::actions;
+    use correspondence_arcs::data::violations::RuleViolation;
 
     #[test]
     fn move_1_ship() {
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
@@ -21,9 +22,9 @@ mod test {
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Move { origin_id: origin_system, destination_id: destination_system, fresh_ships: 1, damaged_ships: 0 }}
-        ]);
+        ]).unwrap();
 
-        match &new_game_state.systems[origin_system as usize] {
+        match &new_game_state.systems()[origin_system as usize] {
             System::Used {ships, controlled_by, ..} => {
                 assert_eq!(ships.clone(), [(Color::Red, Ships { fresh: 2, damaged: 0 }), (Color::Blue, Ships {  fresh: 0, damaged: 0 })].iter().cloned().collect());
                 assert_eq!(controlled_by, &Some(Color::Red))
@@ -31,7 +32,7 @@ mod test {
             _ => panic!("Expected Used System Variant")
         }
 
-        match &new_game_state.systems[destination_system as usize] {
+        match &new_game_state.systems()[destination_system as usize] {
             System::Used {ships, controlled_by, ..} => {
                 assert_eq!(ships.clone(), [(Color::Red, Ships { fresh: 1, damaged: 0 }), (Color::Blue, Ships {  fresh: 0, damaged: 0 })].iter().cloned().collect());
                 assert_eq!(controlled_by, &Some(Color::Red))
@@ -44,7 +45,7 @@ mod test {
     #[test]
     fn move_all_ships() {
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
@@ -55,9 +56,9 @@ mod test {
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Move { origin_id: origin_system, destination_id: destination_system, fresh_ships: 3, damaged_ships: 0 }}
-        ]);
+        ]).unwrap();
 
-        match &new_game_state.systems[origin_system as usize] {
+        match &new_game_state.systems()[origin_system as usize] {
             System::Used {ships, controlled_by, ..} => {
                 assert_eq!(ships.clone(), [(Color::Red, Ships { fresh: 0, damaged: 0 }), (Color::Blue, Ships { fresh: 0, damaged: 0 })].iter().cloned().collect());
                 assert_eq!(controlled_by, &None)
@@ -65,7 +66,7 @@ mod test {
             _ => panic!("Expected Used System Variant")
         }
 
-        match &new_game_state.systems[destination_system as usize] {
+        match &new_game_state.systems()[destination_system as usize] {
             System::Used {ships, controlled_by, ..} => {
                 assert_eq!(ships.clone(), [(Color::Red, Ships { fresh: 3, damaged: 0 }), (Color::Blue, Ships {  fresh: 0, damaged: 0 })].iter().cloned().collect());
                 assert_eq!(controlled_by, &Some(Color::Red))
@@ -76,21 +77,93 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "Destination not connected to Origin")]
     fn move_to_non_adjacent_system(){
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
         let origin_system = 17;
         let destination_system = 18;
 
-        let _ = actions::execute_actions(&game_state, vec![
+        let result = actions::execute_actions(&game_state, vec![
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Move { origin_id: origin_system, destination_id: destination_system, fresh_ships: 3, damaged_ships: 0 }}
         ]);
+        assert_eq!(result, Err(RuleViolation::NotConnected { origin: 17, destination: 18 }));
     }
 
-}
\ No newline at end of file
+    fn move_fleets(game_state: &GameState, destination_id: u8, fleets: Vec<(Vec<u8>,u8,u8)>) -> Result<GameState, RuleViolation> {
+        let mut game_state = game_state.clone();
+        game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
+
+        actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
+            Action::EndPrelude,
+            Action::MainAction {basic_action: BasicAction::MoveFleets { destination_id: destination_id, fleets: fleets }}
+        ])
+    }
+
+    #[test]
+    fn move_fleets_from_several_origins() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        let new_game_state = move_fleets(&game_state, 13, vec![(vec![17, 3, 2], 2, 0), (vec![2], 1, 0), (vec![14], 2, 0)]).unwrap();
+
+        assert_eq!(new_game_state.turn_state, TurnState::Actions { action_type: ActionType::Mobilization, pips_left: 3 });
+        assert_eq!(new_game_state.systems()[17].get_all_ships(&Color::Red), 1);
+        assert_eq!(new_game_state.systems()[3].get_all_ships(&Color::Red), 0);
+        assert_eq!(new_game_state.systems()[2].get_all_ships(&Color::Red), 1);
+        assert_eq!(new_game_state.systems()[14].get_all_ships(&Color::Red), 0);
+        match &new_game_state.systems()[13] {
+            System::Used {ships, controlled_by, ..} => {
+                assert_eq!(ships.clone(), [(Color::Red, Ships { fresh: 5, damaged: 0 }), (Color::Blue, Ships {  fresh: 0, damaged: 0 })].iter().cloned().collect());
+                assert_eq!(controlled_by, &Some(Color::Red))
+            }
+            _ => panic!("Expected Used System Variant")
+        }
+    }
+
+    #[test]
+    fn move_fleet_through_rival_controlled_gate() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(move_fleets(&game_state, 16, vec![(vec![20, 4, 3], 1, 0)]), Err(RuleViolation::GateNotPassable { system_id: 4 }));
+    }
+
+    #[test]
+    fn move_fleet_through_planet() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(move_fleets(&game_state, 15, vec![(vec![17, 16], 1, 0)]), Err(RuleViolation::GateNotPassable { system_id: 16 }));
+    }
+
+    #[test]
+    fn move_fleet_along_broken_path() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(move_fleets(&game_state, 12, vec![(vec![17, 2], 1, 0)]), Err(RuleViolation::NotConnected { origin: 17, destination: 2 }));
+    }
+
+    #[test]
+    fn move_fleet_back_into_origin() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(move_fleets(&game_state, 2, vec![(vec![2, 1], 1, 0)]), Err(RuleViolation::ReturnToOrigin { system_id: 2 }));
+    }
+
+    #[test]
+    fn move_fleets_with_too_many_ships() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(move_fleets(&game_state, 2, vec![(vec![17, 3], 2, 0), (vec![17, 3], 2, 0)]), Err(RuleViolation::NotEnoughShips { system_id: 17, fresh: 4, damaged: 0, fresh_present: 3, damaged_present: 0 }));
+    }
+
+    #[test]
+    fn move_fleets_with_more_ships_than_fit_in_a_count() {
+        let game_state = board::setup_game(&two_player_frontiers()).unwrap();
+
+        assert_eq!(move_fleets(&game_state, 3, vec![(vec![17], 200, 100), (vec![17], 100, 200)]), Err(RuleViolation::NotEnoughShips { system_id: 17, fresh: 255, damaged: 255, fresh_present: 3, damaged_present: 0 }));
+    }
+}
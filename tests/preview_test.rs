@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::two_player_frontiers;
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, BuildType, Color, Dice, GameState, ResourceType, TurnState};
+    use correspondence_arcs::data::preview::{BuildingChange, ControlChange, NextTurnState, ResourceChange, ShipChange};
+    use correspondence_arcs::data::system::BuildingType;
+    use correspondence_arcs::data::violations::RuleViolation;
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+
+    fn card(action_type: ActionType) -> ActionCard {
+        ActionCard { action_type: action_type, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) }
+    }
+
+    fn in_actions(action_type: ActionType) -> GameState {
+        let mut game_state = board::setup_game(&two_player_frontiers()).unwrap();
+        game_state.add_action_cards(&Color::Red, vec![card(action_type.clone())]);
+        actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(action_type), declare: None },
+            Action::EndPrelude
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_preview_move(){
+        let game_state = in_actions(ActionType::Agression);
+        let action = Action::MainAction { basic_action: BasicAction::Move { origin_id: 17, destination_id: 3, fresh_ships: 3, damaged_ships: 0 } };
+
+        let preview = actions::validate_action(&game_state, action.clone()).unwrap();
+
+        assert_eq!(preview.ships, vec![
+            ShipChange { system_id: 3, player: Color::Red, fresh: 3, damaged: 0 },
+            ShipChange { system_id: 17, player: Color::Red, fresh: -3, damaged: 0 }
+        ]);
+        assert_eq!(preview.control, vec![
+            ControlChange { system_id: 3, before: None, after: Some(Color::Red) },
+            ControlChange { system_id: 17, before: Some(Color::Red), after: None }
+        ]);
+        assert!(preview.buildings.is_empty());
+        assert!(preview.resources.is_empty());
+        assert_eq!(preview.turn_state, NextTurnState::Known { turn_state: TurnState::Actions { action_type: ActionType::Agression, pips_left: 2 } });
+        assert_eq!(preview.turn_state, NextTurnState::Known { turn_state: actions::execute_action(&game_state, action).unwrap().turn_state });
+    }
+
+    #[test]
+    fn test_preview_build_starport(){
+        let game_state = in_actions(ActionType::Construction);
+
+        let preview = actions::validate_action(&game_state, Action::MainAction { basic_action: BasicAction::Build { target_system: 20, build_type: BuildType::Starport } }).unwrap();
+
+        assert_eq!(preview.buildings, vec![BuildingChange { system_id: 20, player: Color::Red, building_type: BuildingType::Starport, fresh: 1, damaged: 0 }]);
+        assert!(preview.ships.is_empty());
+        assert!(preview.control.is_empty());
+    }
+
+    #[test]
+    fn test_preview_taxed_resource(){
+        let game_state = in_actions(ActionType::Administration);
+        let g1 = actions::execute_action(&game_state, Action::MainAction { basic_action: BasicAction::Tax { target_system: 20, target_player: Color::Red } }).unwrap();
+
+        let preview = actions::validate_action(&g1, Action::AllocateResources { configuration: vec![(0, ResourceType::Psionics), (1, ResourceType::Material), (2, ResourceType::Psionics)] }).unwrap();
+
+        assert_eq!(preview.resources, vec![ResourceChange { player: Color::Red, resource: ResourceType::Psionics, change: 1 }]);
+        assert_eq!(preview.turn_state, NextTurnState::Known { turn_state: TurnState::Actions { action_type: ActionType::Administration, pips_left: 2 } });
+    }
+
+    #[test]
+    fn test_preview_battle_hides_the_dice(){
+        let game_state = in_actions(ActionType::Agression);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 3, 0).unwrap();
+        let game_state = actions::moving::move_ships(&game_state, 16, 15, 3, 0).unwrap();
+        let action = Action::MainAction { basic_action: BasicAction::Battle { target_system: 15, target_player: Color::Blue, dice: vec![Dice::Skirmish, Dice::Skirmish, Dice::Skirmish] } };
+
+        let preview = actions::validate_action(&game_state, action.clone()).unwrap();
+
+        assert_eq!(preview.turn_state, NextTurnState::DependsOnDice);
+        assert!(!format!("{:?}", preview).contains("hits"));
+        assert!(matches!(actions::execute_action(&game_state, action).unwrap().turn_state, TurnState::AllocateDiceResults { .. }));
+    }
+
+    #[test]
+    fn test_forbidden_action_names_the_rule(){
+        let game_state = in_actions(ActionType::Agression);
+
+        assert_eq!(
+            actions::validate_action(&game_state, Action::MainAction { basic_action: BasicAction::Move { origin_id: 17, destination_id: 18, fresh_ships: 1, damaged_ships: 0 } }),
+            Err(RuleViolation::NotConnected { origin: 17, destination: 18 })
+        );
+    }
+
+    #[test]
+    fn test_preview_is_readable(){
+        let game_state = in_actions(ActionType::Agression);
+
+        let preview = actions::validate_action(&game_state, Action::MainAction { basic_action: BasicAction::Move { origin_id: 17, destination_id: 3, fresh_ships: 1, damaged_ships: 0 } }).unwrap();
+
+        assert_eq!(preview.to_string(), "Red Ships in System 3: +1 fresh, +0 damaged\nRed Ships in System 17: -1 fresh, +0 damaged\nControl of System 3: None -> Some(Red)\nNext: Actions { action_type: Agression, pips_left: 2 }");
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod test {
+    use correspondence_arcs::data::reach::Reach;
+    use correspondence_arcs::data::setup_cards::{get_setup_card, setup_card_names, two_player_frontiers};
+    use correspondence_arcs::data::system::System;
+
+    use correspondence_arcs::board::setup_game_with_set_seed;
+
+    #[test]
+    fn clusters_and_gates() {
+        let reach = Reach::new(&[0, 5]);
+
+        assert_eq!(reach.gates(), vec![1, 2, 3, 4]);
+        assert_eq!(reach.systems_in_cluster(3), vec![3, 15, 16, 17]);
+        assert_eq!(reach.systems_in_cluster(0), Vec::<u8>::new());
+        assert_eq!(reach.systems().len(), 16);
+        assert_eq!(reach.planets().len(), 12);
+        assert_eq!(Reach::cluster_of(17), 3);
+        assert_eq!(Reach::cluster_of(4), 4);
+        assert!(Reach::is_gate(5));
+        assert!(!reach.is_in_play(6));
+    }
+
+    #[test]
+    fn neighbours_skip_clusters_out_of_play() {
+        let reach = Reach::new(&[0, 5]);
+
+        assert_eq!(reach.neighbours(1), &vec![4, 2, 9, 10, 11]);
+        assert_eq!(reach.neighbours(4), &vec![3, 1, 18, 19, 20]);
+        assert_eq!(reach.neighbours(20), &vec![4, 19]);
+        assert_eq!(reach.neighbours(11), &vec![1, 12, 10]);
+        assert!(reach.are_connected(17, 16));
+        assert!(!reach.are_connected(17, 18));
+    }
+
+    #[test]
+    fn neighbours_match_systems_of_setup() {
+        for players in 2..5 {
+            for name in setup_card_names(players) {
+                let setup_card = get_setup_card(name, players).unwrap();
+                let game_state = setup_game_with_set_seed(&setup_card, 0).unwrap();
+                assert_eq!(Reach::from_systems(game_state.systems()), game_state.reach);
+                for system in game_state.systems().iter() {
+                    if let System::Used { system_id, connects_to, .. } = system {
+                        assert_eq!(game_state.reach.neighbours(*system_id), connects_to);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let reach = setup_game_with_set_seed(&two_player_frontiers(), 0).unwrap().reach;
+
+        assert_eq!(reach.shortest_path(17, 17), Some(vec![17]));
+        assert_eq!(reach.shortest_path(17, 20), Some(vec![17, 3, 4, 20]));
+        assert_eq!(reach.distance(17, 12), Some(3));
+        assert_eq!(reach.shortest_path(17, 6), None);
+        assert_eq!(reach.distance(17, 6), None);
+    }
+
+    #[test]
+    fn reachable_through_passable_gates() {
+        let reach = Reach::new(&[0, 5]);
+
+        assert_eq!(reach.reachable_through_gates(17, |_| false), vec![3, 16]);
+        assert_eq!(reach.reachable_through_gates(17, |g| g == 3), vec![2, 3, 4, 15, 16]);
+        assert_eq!(reach.reachable_through_gates(17, |_| true), vec![1, 2, 3, 4, 9, 10, 11, 12, 13, 14, 15, 16, 18, 19, 20]);
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::{SetupOptions, two_player_frontiers};
+
+    use correspondence_arcs::data::game_state::{Action, GameState, TurnState};
+    use correspondence_arcs::data::violations::RuleViolation;
+    use correspondence_arcs::replay::{digest, Divergence, GameRecord};
+
+    // Every player follows with the first card in hand and ends the turn without actions
+    fn next_action(game_state: &GameState) -> Option<Action> {
+        let hand = game_state.get_player_area(&game_state.current_player).action_cards;
+        match game_state.turn_state {
+            TurnState::TrickTaking if game_state.lead_card.is_none() => hand.first().map(|c| Action::PlayLeadCard { card: c.clone(), declare: None }),
+            TurnState::TrickTaking => hand.first().map(|c| Action::Copy { card: c.clone(), seize: None }),
+            TurnState::Prelude { .. } => Some(Action::EndPrelude),
+            TurnState::Actions { .. } => Some(Action::EndTurn),
+            _ => None
+        }
+    }
+
+    fn play(steps: usize) -> (GameRecord, GameState) {
+        let record = GameRecord::new(&two_player_frontiers(), 12, &SetupOptions::default()).unwrap();
+        let game_state = record.initial_state().unwrap();
+        (0..steps).fold((record, game_state), |(record, game_state), _| match next_action(&game_state) {
+            Some(action) => record.record(&game_state, action).unwrap(),
+            None => (record, game_state)
+        })
+    }
+
+    #[test]
+    fn test_replay_rebuilds_every_state(){
+        let (record, game_state) = play(72);
+
+        let states = record.replay().unwrap();
+
+        assert_eq!(states.len(), record.actions.len() + 1);
+        assert_eq!(states[0], record.initial_state().unwrap());
+        assert_eq!(states.last().unwrap(), &game_state);
+        assert_eq!(record.final_state().unwrap(), game_state);
+        assert_eq!(game_state.chapter, 2);
+    }
+
+    #[test]
+    fn test_rejected_action_is_reported(){
+        let (mut record, _) = play(10);
+        record.actions[3].action = Action::EndPrelude;
+
+        match record.replay().map_err(|d| *d) {
+            Err(Divergence::Rejected { index, action, violation }) => {
+                assert_eq!(index, 3);
+                assert_eq!(action, Action::EndPrelude);
+                assert_eq!(violation, RuleViolation::WrongTurnState { turn_state: TurnState::TrickTaking, action: Action::EndPrelude });
+            },
+            r => panic!("Expected a rejected action, got {:?}", r)
+        }
+    }
+
+    #[test]
+    fn test_different_events_are_reported(){
+        let (mut record, _) = play(10);
+        record.actions[3].events = vec![];
+
+        match record.replay().map_err(|d| *d) {
+            Err(Divergence::DifferentEvents { index, recorded, replayed, .. }) => {
+                assert_eq!(index, 3);
+                assert_eq!(recorded, vec![]);
+                assert!(!replayed.is_empty());
+            },
+            r => panic!("Expected different events, got {:?}", r)
+        }
+    }
+
+    #[test]
+    fn test_other_seed_diverges(){
+        let (record, _) = play(5);
+        let other_seed = GameRecord { seed: 13, ..record.clone() };
+
+        assert!(matches!(other_seed.replay().map_err(|d| *d), Err(Divergence::DifferentInitialState { .. })));
+    }
+
+    #[test]
+    fn test_equal_states_have_equal_digests(){
+        let (record, game_state) = play(40);
+        let replayed = record.final_state().unwrap();
+
+        assert_eq!(digest(&replayed), digest(&game_state));
+        assert_ne!(digest(&replayed), digest(&record.initial_state().unwrap()));
+    }
+
+    #[test]
+    fn test_different_state_is_reported(){
+        let (mut record, _) = play(10);
+        record.actions[3].digest += 1;
+
+        match record.replay().map_err(|d| *d) {
+            Err(Divergence::DifferentState { index, recorded, replayed, .. }) => {
+                assert_eq!(index, 3);
+                assert_eq!(recorded, replayed + 1);
+            },
+            r => panic!("Expected a different state, got {:?}", r)
+        }
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::two_player_frontiers;
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, Color, Dice, GameState};
+    use correspondence_arcs::data::court_cards::create_court_deck;
+    use correspondence_arcs::data::rng::GameRng;
+    use correspondence_arcs::data::rules::create_action_deck;
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+
+    fn battle(seed: u64) -> GameState {
+        let card = ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) };
+        let mut game_state = board::setup_game_with_set_seed(&two_player_frontiers(), seed).unwrap();
+        game_state.add_action_cards(&Color::Red, vec![card.clone()]);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 3, 0).unwrap();
+        let game_state = actions::moving::move_ships(&game_state, 16, 15, 3, 0).unwrap();
+
+        actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card, declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: BasicAction::Battle { target_system: 15, target_player: Color::Blue, dice: vec![Dice::Assault, Dice::Assault, Dice::Raid] } }
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_battle_is_reproducible(){
+        let first = battle(7);
+        let second = battle(7);
+
+        assert_eq!(first.turn_state, second.turn_state);
+        assert_eq!(first.rng, second.rng);
+        assert_ne!(first.rng, GameRng::new(7));
+    }
+
+    #[test]
+    fn test_rolls_advance_the_stream(){
+        let rng = GameRng::new(3);
+
+        let (first, rng) = rng.rolls(20, 6);
+        let (second, _) = rng.rolls(20, 6);
+
+        assert_ne!(first, second);
+        assert!(first.iter().chain(second.iter()).all(|r| *r < 6));
+        assert_eq!(GameRng::new(3).rolls(20, 6).0, first);
+    }
+
+    #[test]
+    fn test_shuffle_is_a_permutation(){
+        let items: Vec<u8> = (0..30).collect();
+
+        let (shuffled, _) = GameRng::new(11).shuffle(&items);
+
+        assert_ne!(shuffled, items);
+        assert_eq!(shuffled.iter().cloned().collect::<std::collections::BTreeSet<u8>>().len(), 30);
+        assert_eq!(GameRng::new(11).shuffle(&items).0, shuffled);
+    }
+
+    #[test]
+    fn test_court_reshuffle_is_reproducible(){
+        let mut game_state = board::setup_game_with_set_seed(&two_player_frontiers(), 5).unwrap();
+        game_state.court_discard_pile = game_state.court_draw_pile.clone();
+        game_state.court_draw_pile = vec![];
+
+        let first = game_state.reshuffle_court_discard_pile();
+        let second = game_state.reshuffle_court_discard_pile();
+
+        assert_eq!(first.court_draw_pile, second.court_draw_pile);
+        assert_eq!(first.court_draw_pile.len(), game_state.court_discard_pile.len());
+        assert_ne!(first.rng, game_state.rng);
+    }
+
+    #[test]
+    fn test_setup_shuffles_from_the_stream(){
+        let game_state = board::setup_game_with_set_seed(&two_player_frontiers(), 5).unwrap();
+
+        let (action_deck, rng) = GameRng::new(5).shuffle(&create_action_deck(&game_state.rules));
+        assert_eq!(game_state.action_deck, action_deck);
+        let (court_deck, rng) = create_court_deck(game_state.seating.clone(), &rng);
+        assert_eq!(game_state.court.iter().chain(&game_state.court_draw_pile).cloned().collect::<Vec<_>>(), court_deck);
+        assert_eq!(game_state.rng, rng);
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::two_player_frontiers;
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, BuildType, Color, Dice, GameState, TurnState};
+    use correspondence_arcs::data::violations::RuleViolation;
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+
+    fn card(action_type: ActionType) -> ActionCard {
+        ActionCard { action_type: action_type, number: 2, pips: 1, declared_ambition: Some(AmbitionTypes::Tycoon) }
+    }
+
+    fn setup_with_card(action_type: ActionType) -> GameState {
+        let mut game_state = board::setup_game(&two_player_frontiers()).unwrap();
+        game_state.add_action_cards(&Color::Red, vec![card(action_type)]);
+        game_state
+    }
+
+    fn main_action(action_type: ActionType, basic_action: BasicAction) -> Result<GameState, RuleViolation> {
+        actions::execute_actions(&setup_with_card(action_type.clone()), vec![
+            Action::PlayLeadCard { card: card(action_type), declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: basic_action }
+        ])
+    }
+
+    #[test]
+    fn test_action_in_wrong_turn_state(){
+        let game_state = setup_with_card(ActionType::Construction);
+
+        assert_eq!(
+            actions::execute_action(&game_state, Action::EndPrelude),
+            Err(RuleViolation::WrongTurnState { turn_state: TurnState::TrickTaking, action: Action::EndPrelude })
+        );
+    }
+
+    #[test]
+    fn test_play_card_not_in_hand(){
+        let game_state = setup_with_card(ActionType::Construction);
+
+        assert_eq!(
+            actions::execute_action(&game_state, Action::PlayLeadCard { card: card(ActionType::Agression), declare: None }),
+            Err(RuleViolation::CardNotInHand { player: Color::Red, card: card(ActionType::Agression) })
+        );
+    }
+
+    #[test]
+    fn test_seize_with_card_not_in_hand(){
+        let mut game_state = setup_with_card(ActionType::Construction);
+        game_state.add_action_cards(&Color::Blue, vec![card(ActionType::Agression)]);
+        let seize = ActionCard { action_type: ActionType::Mobilization, number: 4, pips: 4, declared_ambition: None };
+
+        let result = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Construction), declare: None },
+            Action::EndPrelude,
+            Action::EndTurn,
+            Action::Copy { card: card(ActionType::Agression), seize: Some(seize.clone()) }
+        ]);
+        assert_eq!(result, Err(RuleViolation::CardNotInHand { player: Color::Blue, card: seize }));
+    }
+
+    #[test]
+    fn test_action_not_on_card(){
+        let game_state = setup_with_card(ActionType::Agression);
+        let build = BasicAction::Build { target_system: 17, build_type: BuildType::Ship };
+
+        let result = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Agression), declare: None },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: build.clone() }
+        ]);
+        assert_eq!(result, Err(RuleViolation::NotOnActionCard { action_type: ActionType::Agression, action: build }));
+    }
+
+    #[test]
+    fn test_no_action_pips_left(){
+        let game_state = setup_with_card(ActionType::Construction);
+        let build = Action::MainAction { basic_action: BasicAction::Build { target_system: 17, build_type: BuildType::Ship } };
+
+        let result = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Construction), declare: None },
+            Action::EndPrelude,
+            build.clone(),
+            build
+        ]);
+        assert_eq!(result, Err(RuleViolation::NoActionPips { turn_state: TurnState::Actions { action_type: ActionType::Construction, pips_left: 0 } }));
+    }
+
+    #[test]
+    fn test_declare_ambition_not_on_card(){
+        let game_state = setup_with_card(ActionType::Construction);
+
+        assert_eq!(
+            actions::execute_action(&game_state, Action::PlayLeadCard { card: card(ActionType::Construction), declare: Some(AmbitionTypes::Warlord) }),
+            Err(RuleViolation::CannotDeclareAmbition { card: card(ActionType::Construction), ambition: AmbitionTypes::Warlord })
+        );
+    }
+
+    #[test]
+    fn test_declaring_plays_the_card(){
+        let game_state = setup_with_card(ActionType::Construction);
+
+        let new_game_state = actions::execute_action(&game_state, Action::PlayLeadCard { card: card(ActionType::Construction), declare: Some(AmbitionTypes::Tycoon) }).unwrap();
+
+        assert!(new_game_state.get_player_area(&Color::Red).action_cards.is_empty());
+        assert_eq!(new_game_state.turn_state, TurnState::Prelude { action_type: ActionType::Construction, pips_left: 1 });
+    }
+
+    #[test]
+    fn test_violation_is_readable(){
+        assert_eq!(RuleViolation::NotConnected { origin: 17, destination: 18 }.to_string(), "System 18 is not connected to System 17");
+    }
+
+    #[test]
+    fn test_system_not_in_play(){
+        let unused = Err(RuleViolation::UnusedSystem { system_id: 99 });
+
+        assert_eq!(main_action(ActionType::Construction, BasicAction::Build { target_system: 99, build_type: BuildType::Ship }), unused);
+        assert_eq!(main_action(ActionType::Construction, BasicAction::Repair { target_system: 99, build_type: BuildType::Ship }), unused);
+        assert_eq!(main_action(ActionType::Administration, BasicAction::Tax { target_system: 99, target_player: Color::Red }), unused);
+        assert_eq!(main_action(ActionType::Agression, BasicAction::Catapult { origin_system: 99, destination_systems: vec![(3, 1, 0)] }), unused);
+        assert_eq!(main_action(ActionType::Agression, BasicAction::Battle { target_system: 99, target_player: Color::Blue, dice: vec![Dice::Skirmish] }), unused);
+    }
+
+    #[test]
+    fn test_player_not_seated(){
+        let unknown = Err(RuleViolation::UnknownPlayer { player: Color::Yellow });
+
+        assert_eq!(main_action(ActionType::Administration, BasicAction::Tax { target_system: 20, target_player: Color::Yellow }), unknown);
+        assert_eq!(main_action(ActionType::Agression, BasicAction::Battle { target_system: 17, target_player: Color::Yellow, dice: vec![Dice::Skirmish] }), unknown);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::{two_player_frontiers, four_player_frontiers, SetupOptions};
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, Color, GameState, ReserveType, ResourceSlot, TurnState};
+    use correspondence_arcs::data::rules::{create_action_deck, RuleConfig};
+    use correspondence_arcs::data::violations::RuleViolation;
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+
+    fn setup_with_rules(rules: RuleConfig) -> GameState {
+        board::setup_game_with_options(&two_player_frontiers(), 0, &SetupOptions { rules: Some(rules), ..SetupOptions::default() }).unwrap()
+    }
+
+    #[test]
+    fn test_default_rules(){
+        let game_state = board::setup_game_with_set_seed(&two_player_frontiers(), 0).unwrap();
+        assert_eq!(game_state.rules, RuleConfig::for_players(2));
+        assert_eq!(game_state.ambition_markers, game_state.rules.ambition_markers);
+        assert_eq!(game_state.action_deck.len(), 20);
+        assert!(game_state.action_deck.iter().all(|c| c.number != 1 && c.number != 7));
+
+        let game_state = board::setup_game_with_set_seed(&four_player_frontiers(), 0).unwrap();
+        assert_eq!(game_state.court.len(), 4);
+        assert_eq!(game_state.action_deck.len(), 28);
+    }
+
+    #[test]
+    fn test_action_deck(){
+        let rules = RuleConfig { remove_ones_and_sevens: false, ..RuleConfig::for_players(2) };
+        let deck = create_action_deck(&rules);
+        assert_eq!(deck.len(), 28);
+        assert_eq!(deck.iter().filter(|c| c.declared_ambition.is_none()).count(), 8);
+
+        let game_state = setup_with_rules(rules);
+        assert_eq!(game_state.action_deck.len(), 28);
+        let game_state = game_state.deal_action_cards();
+        assert_eq!(game_state.action_deck.len(), 16);
+        assert_eq!(game_state.get_player_area(&Color::Red).action_cards.len(), 6);
+        assert_eq!(game_state.get_player_area(&Color::Blue).action_cards.len(), 6);
+    }
+
+    #[test]
+    fn test_house_rule_court_and_slots(){
+        let game_state = setup_with_rules(RuleConfig { court_size: 5, open_slots: vec![6, 6, 6, 6, 4, 3], ..RuleConfig::for_players(2) });
+
+        assert_eq!(game_state.court.len(), 5);
+        let red = game_state.get_player_area(&Color::Red);
+        assert_eq!(red.reserve.get(&ReserveType::Cities), Some(&4));
+        assert_eq!(red.resource_slots.iter().filter(|s| !matches!(s, ResourceSlot::Covered { .. })).count(), 4);
+    }
+
+    #[test]
+    fn test_fixed_chapter_count(){
+        let mut game_state = setup_with_rules(RuleConfig { power_to_win: None, chapters: 3, ..RuleConfig::for_players(2) });
+        game_state.players.get_mut(&Color::Red).unwrap().power = 50;
+        assert!(!game_state.is_game_over());
+
+        game_state.chapter = 4;
+        assert!(game_state.is_game_over());
+
+        let mut game_state = board::setup_game_with_set_seed(&two_player_frontiers(), 0).unwrap();
+        game_state.players.get_mut(&Color::Red).unwrap().power = 33;
+        assert!(game_state.is_game_over());
+    }
+
+    fn play_last_round(game_state: &GameState) -> GameState {
+        let card = |action_type: ActionType| ActionCard { action_type: action_type, number: 3, pips: 3, declared_ambition: None };
+        let mut game_state = game_state.clone();
+        game_state.add_action_cards(&Color::Red, vec![card(ActionType::Construction)]);
+        game_state.add_action_cards(&Color::Blue, vec![card(ActionType::Agression)]);
+
+        actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card(ActionType::Construction), declare: None },
+            Action::EndPrelude,
+            Action::EndTurn,
+            Action::Copy { card: card(ActionType::Agression), seize: None },
+            Action::EndPrelude,
+            Action::EndTurn
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_game_ends_after_the_last_chapter(){
+        let mut game_state = setup_with_rules(RuleConfig { chapters: 3, ..RuleConfig::for_players(2) });
+        game_state.chapter = 2;
+        let new_game_state = play_last_round(&game_state);
+        assert_eq!(new_game_state.chapter, 3);
+        assert_eq!(new_game_state.turn_state, TurnState::TrickTaking);
+
+        game_state.chapter = 3;
+        let new_game_state = play_last_round(&game_state);
+        assert_eq!(new_game_state.chapter, 4);
+        assert_eq!(new_game_state.turn_state, TurnState::GameOver);
+    }
+
+    #[test]
+    fn test_game_ends_after_the_chapter_reaching_the_power_to_win(){
+        let mut game_state = setup_with_rules(RuleConfig { power_to_win: Some(10), ..RuleConfig::for_players(2) });
+        game_state.players.get_mut(&Color::Red).unwrap().power = 10;
+        let game_state = play_last_round(&game_state);
+        assert_eq!(game_state.turn_state, TurnState::GameOver);
+    }
+
+    #[test]
+    fn test_no_actions_after_the_game(){
+        let game_state = play_last_round(&setup_with_rules(RuleConfig { chapters: 1, ..RuleConfig::for_players(2) }));
+        assert_eq!(actions::execute_action(&game_state, Action::EndTurn), Err(RuleViolation::WrongTurnState { turn_state: TurnState::GameOver, action: Action::EndTurn }));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot deal 6 cards to 2 players")]
+    fn test_deal_from_small_deck(){
+        setup_with_rules(RuleConfig::for_players(2)).deal_action_cards().deal_action_cards();
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::{two_player_frontiers, three_player_frontiers, Seating, SetupError, SetupOptions};
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, Color, GameState, TurnState};
+    use correspondence_arcs::data::rng::GameRng;
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+
+    fn setup_three_players(options: SetupOptions) -> GameState {
+        board::setup_game_with_options(&three_player_frontiers(), 5, &options).unwrap()
+    }
+
+    #[test]
+    fn test_default_seating(){
+        let game_state = board::setup_game_with_set_seed(&three_player_frontiers(), 5).unwrap();
+
+        assert_eq!(game_state.seating, vec![Color::Red, Color::Blue, Color::White]);
+        assert_eq!(game_state.current_player, Color::Red);
+        assert_eq!(game_state.next_player(&Color::White), Color::Red);
+        assert!(game_state.get_player_area(&Color::Red).initiative);
+    }
+
+    #[test]
+    fn test_choose_first_player(){
+        let game_state = setup_three_players(SetupOptions { first_player: Some(Color::Blue), ..SetupOptions::default() });
+
+        assert_eq!(game_state.seating, vec![Color::Blue, Color::White, Color::Red]);
+        assert_eq!(game_state.current_player, Color::Blue);
+        assert_eq!(game_state.initiative, Color::Blue);
+        assert!(game_state.get_player_area(&Color::Blue).initiative);
+        assert!(!game_state.get_player_area(&Color::Red).initiative);
+        // The first player gets the first A location
+        assert_eq!(game_state.systems()[12].get_fresh_ships(&Color::Blue), 3);
+    }
+
+    #[test]
+    fn test_chosen_colors(){
+        let game_state = board::setup_game_with_options(&two_player_frontiers(), 5, &SetupOptions { seating: Seating::Chosen { seats: vec![Color::Yellow, Color::White] }, ..SetupOptions::default() }).unwrap();
+
+        assert_eq!(game_state.seating, vec![Color::Yellow, Color::White]);
+        assert_eq!(game_state.current_player, Color::Yellow);
+        assert!(game_state.players.contains_key(&Color::White));
+        assert!(!game_state.players.contains_key(&Color::Red));
+        assert_eq!(game_state.systems()[20].get_fresh_ships(&Color::Yellow), 3);
+    }
+
+    #[test]
+    fn test_chosen_colors_twice(){
+        let errors = board::setup_game_with_options(&two_player_frontiers(), 5, &SetupOptions { seating: Seating::Chosen { seats: vec![Color::Blue, Color::Blue] }, ..SetupOptions::default() }).unwrap_err();
+        assert_eq!(errors, vec![SetupError::InvalidSeating { players: 2, seats: vec![Color::Blue, Color::Blue] }]);
+    }
+
+    #[test]
+    fn test_first_player_not_seated(){
+        let errors = board::setup_game_with_options(&two_player_frontiers(), 5, &SetupOptions { first_player: Some(Color::White), ..SetupOptions::default() }).unwrap_err();
+        assert_eq!(errors, vec![SetupError::FirstPlayerNotSeated { player: Color::White, seats: vec![Color::Red, Color::Blue] }]);
+    }
+
+    #[test]
+    fn test_random_seating(){
+        let seats = Seating::Random.seats(4, &GameRng::new(9)).0;
+        assert_eq!(seats, Seating::Random.seats(4, &GameRng::new(9)).0);
+        assert!([Color::Red, Color::Blue, Color::White, Color::Yellow].iter().all(|c| seats.contains(c)));
+
+        let game_state = setup_three_players(SetupOptions { seating: Seating::Random, ..SetupOptions::default() });
+        let mut seated = game_state.seating.clone();
+        seated.sort_by_key(|c| format!("{:?}", c));
+        assert_eq!(seated, vec![Color::Blue, Color::Red, Color::White]);
+        assert_eq!(game_state.current_player, game_state.seating[0]);
+    }
+
+    #[test]
+    fn test_turn_passes_along_seating(){
+        let mut game_state = setup_three_players(SetupOptions { seating: Seating::Chosen { seats: vec![Color::White, Color::Red, Color::Blue] }, ..SetupOptions::default() });
+        let card = ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) };
+        game_state.add_action_cards(&Color::White, vec![card.clone()]);
+
+        let new_game_state = actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card, declare: None },
+            Action::EndPrelude,
+            Action::EndTurn
+        ]).unwrap();
+
+        assert_eq!(new_game_state.turn_state, TurnState::TrickTaking);
+        assert_eq!(new_game_state.current_player, Color::Red);
+    }
+}
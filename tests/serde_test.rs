@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::{SetupOptions, two_player_frontiers};
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, BuildType, Color, Dice, GameState, TurnState};
+    use correspondence_arcs::data::legal_actions::LegalActions;
+    use correspondence_arcs::replay::GameRecord;
+    use correspondence_arcs::save::{SaveError, SaveFile, SAVE_VERSION};
+    use correspondence_arcs::board;
+    use correspondence_arcs::actions;
+
+    fn after_battle() -> GameState {
+        let card = ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) };
+        let mut game_state = board::setup_game_with_set_seed(&two_player_frontiers(), 8).unwrap();
+        game_state.add_action_cards(&Color::Red, vec![card.clone()]);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 3, 0).unwrap();
+        let game_state = actions::moving::move_ships(&game_state, 16, 15, 3, 0).unwrap();
+        actions::execute_actions(&game_state, vec![
+            Action::PlayLeadCard { card: card, declare: Some(AmbitionTypes::Tycoon) },
+            Action::EndPrelude,
+            Action::MainAction { basic_action: BasicAction::Battle { target_system: 15, target_player: Color::Blue, dice: vec![Dice::Assault, Dice::Raid] } }
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_game_state_round_trip(){
+        let game_state = after_battle();
+
+        let json = SaveFile::new(&game_state).to_json();
+        let loaded = SaveFile::from_json(&json).unwrap();
+
+        assert_eq!(loaded.version, SAVE_VERSION);
+        assert_eq!(loaded.game_state, game_state);
+    }
+
+    #[test]
+    fn test_actions_round_trip(){
+        let record = GameRecord::new(&two_player_frontiers(), 8, &SetupOptions::default()).unwrap();
+        let game_state = record.initial_state().unwrap();
+        let lead = actions::legal_actions(&game_state).iter().next().unwrap();
+        let game_state = actions::execute_actions(&game_state, vec![lead, Action::EndPrelude]).unwrap();
+        let legal_actions = actions::legal_actions(&game_state);
+
+        let json = serde_json::to_string(&legal_actions).unwrap();
+        assert_eq!(serde_json::from_str::<LegalActions>(&json).unwrap(), legal_actions);
+        for action in legal_actions.iter() {
+            let json = serde_json::to_string(&action).unwrap();
+            assert_eq!(serde_json::from_str::<Action>(&json).unwrap(), action);
+        }
+    }
+
+    #[test]
+    fn test_turn_state_round_trip(){
+        let turn_state = after_battle().turn_state;
+        assert!(matches!(turn_state, TurnState::AllocateDiceResults { .. }));
+
+        let json = serde_json::to_string(&turn_state).unwrap();
+
+        assert_eq!(serde_json::from_str::<TurnState>(&json).unwrap(), turn_state);
+    }
+
+    #[test]
+    fn test_action_format_is_stable(){
+        let action = Action::MainAction { basic_action: BasicAction::Build { target_system: 17, build_type: BuildType::Ship } };
+
+        assert_eq!(serde_json::to_string(&action).unwrap(), r#"{"MainAction":{"basic_action":{"Build":{"target_system":17,"build_type":"Ship"}}}}"#);
+    }
+
+    #[test]
+    fn test_unsupported_version(){
+        let json = SaveFile { version: SAVE_VERSION + 1, game_state: after_battle() }.to_json();
+
+        assert_eq!(SaveFile::from_json(&json), Err(SaveError::UnsupportedVersion { found: SAVE_VERSION + 1, supported: SAVE_VERSION }));
+        assert_eq!(SaveFile::from_json("{}"), Err(SaveError::MissingVersion));
+        assert!(matches!(SaveFile::from_json("not json"), Err(SaveError::Malformed { .. })));
+    }
+}
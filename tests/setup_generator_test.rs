@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod test {
+    use correspondence_arcs::data::setup_cards::generate_setup_card;
+
+    use correspondence_arcs::board::setup_game_with_set_seed;
+
+    #[test]
+    fn generated_setup_cards_are_valid() {
+        for players in 2..5 {
+            for seed in 0..20 {
+                let setup_card = generate_setup_card(players, seed);
+                assert_eq!(setup_card.validate(), Ok(()));
+                assert_eq!(setup_card.cluster_out_of_play.len(), 4 - players as usize);
+
+                let game_state = setup_game_with_set_seed(&setup_card, seed).unwrap();
+                assert_eq!(game_state.players.len(), players as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn generated_setup_card_is_reproducible() {
+        assert_eq!(generate_setup_card(3, 42), generate_setup_card(3, 42));
+        assert_ne!(generate_setup_card(3, 42), generate_setup_card(3, 43));
+    }
+
+    #[test]
+    fn starting_resources_are_balanced() {
+        for seed in 0..20 {
+            let setup_card = generate_setup_card(2, seed);
+            let game_state = setup_game_with_set_seed(&setup_card, seed).unwrap();
+            for player in game_state.players.values() {
+                assert_ne!(player.get_resource(0), player.get_resource(1));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot generate SetupCard for 5 players")]
+    fn generate_for_too_many_players() {
+        let _ = generate_setup_card(5, 0);
+    }
+}
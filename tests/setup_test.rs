@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod test {
+    use correspondence_arcs::data::setup_cards::{get_setup_card, setup_card_names, three_player_frontiers, four_player_frontiers, LocationType, SetupCard, SetupError};
+
+    use correspondence_arcs::data::game_state::{Color, ReserveType};
+    use correspondence_arcs::data::system::System;
+    use correspondence_arcs::board::setup_game_with_set_seed;
+
+    #[test]
+    fn every_setup_card_can_start_a_game() {
+        for players in 2..5 {
+            for name in setup_card_names(players) {
+                let setup_card = get_setup_card(name, players).unwrap();
+                let game_state = setup_game_with_set_seed(&setup_card, 0).unwrap();
+
+                assert_eq!(game_state.players.len(), players as usize);
+                assert_eq!(game_state.court.len(), if players == 2 {3} else {4});
+                for player in game_state.players.values() {
+                    assert_eq!(player.reserve.get(&ReserveType::Ships), Some(&5));
+                    let ships_on_board: u8 = game_state.systems().iter().map(|s| s.get_all_ships(&player.player)).sum();
+                    assert_eq!(ships_on_board, 10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_setup_card() {
+        assert!(get_setup_card("Frontiers", 5).is_none());
+        assert!(get_setup_card("Core Conflict", 2).is_none());
+    }
+
+    #[test]
+    fn three_player_reach() {
+        let game_state = setup_game_with_set_seed(&three_player_frontiers(), 0).unwrap();
+
+        assert_eq!(game_state.systems()[0], System::Unused);
+        assert!(game_state.systems()[6..9].iter().all(|s| *s == System::Unused));
+        match &game_state.systems()[1] {
+            System::Used { connects_to, .. } => assert_eq!(connects_to, &vec![5, 2, 9, 10, 11]),
+            _ => panic!("Expected Used System Variant")
+        }
+        assert_eq!(game_state.systems()[13].get_all_ships(&Color::Red), 2);
+        assert_eq!(game_state.systems()[16].get_all_ships(&Color::Blue), 2);
+        assert_eq!(game_state.systems()[19].get_all_ships(&Color::White), 2);
+    }
+
+    #[test]
+    fn four_player_reach() {
+        let game_state = setup_game_with_set_seed(&four_player_frontiers(), 0).unwrap();
+
+        assert!(game_state.systems().iter().all(|s| *s != System::Unused));
+        match &game_state.systems()[11] {
+            System::Used { connects_to, .. } => assert_eq!(connects_to, &vec![1, 12, 10]),
+            _ => panic!("Expected Used System Variant")
+        }
+        assert_eq!(game_state.systems()[17].get_all_ships(&Color::White), 3);
+        assert_eq!(game_state.systems()[23].get_all_ships(&Color::Yellow), 3);
+        assert_eq!(game_state.players.get(&Color::Yellow).unwrap().reserve.get(&ReserveType::Cities), Some(&4));
+    }
+
+    #[test]
+    fn official_setup_cards_are_valid() {
+        for players in 2..5 {
+            for name in setup_card_names(players) {
+                assert_eq!(get_setup_card(name, players).unwrap().validate(), Ok(()), "{} for {} players", name, players);
+            }
+        }
+    }
+
+    #[test]
+    fn validate_location_counts() {
+        let setup_card = SetupCard { players: 2, cluster_out_of_play: vec![0,5], a_locations: vec![20], b_locations: vec![17, 18], c_locations: vec![2,4,14]};
+
+        assert_eq!(setup_card.validate(), Err(vec![
+            SetupError::WrongLocationCount { location_type: LocationType::A, expected: 2, found: 1 },
+            SetupError::WrongLocationCount { location_type: LocationType::C, expected: 4, found: 3 }
+        ]));
+    }
+
+    #[test]
+    fn validate_locations() {
+        let setup_card = SetupCard { players: 2, cluster_out_of_play: vec![0,5], a_locations: vec![7,3], b_locations: vec![17, 18], c_locations: vec![2,4,17,30]};
+
+        assert_eq!(setup_card.validate(), Err(vec![
+            SetupError::LocationOutOfPlay { location_type: LocationType::A, system_id: 7, cluster: 0 },
+            SetupError::GateAsPlanetLocation { location_type: LocationType::A, system_id: 3 },
+            SetupError::UnknownSystem { location_type: LocationType::C, system_id: 30 },
+            SetupError::LocationCollision { system_id: 17 }
+        ]));
+    }
+
+    #[test]
+    fn setup_with_invalid_card() {
+        let setup_card = SetupCard { players: 2, cluster_out_of_play: vec![0,5], a_locations: vec![20,3], b_locations: vec![17, 18], c_locations: vec![2,4,14,15]};
+
+        let errors = setup_game_with_set_seed(&setup_card, 0).unwrap_err();
+
+        assert_eq!(errors, vec![SetupError::GateAsPlanetLocation { location_type: LocationType::A, system_id: 3 }]);
+        assert_eq!(errors[0].to_string(), "A location 3 is a gate, but needs a planet");
+    }
+}
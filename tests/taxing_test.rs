@@ -10,7 +10,7 @@ mod test{
     #[test]
     fn test_tax_own_city(){
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![ActionCard { action_type: ActionType::Administration, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }]);
 
@@ -20,11 +20,11 @@ mod test{
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Administration, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Tax { target_system: target_system, target_player: Color::Red }}
-        ]);
+        ]).unwrap();
 
         assert_eq!(g1.turn_state, TurnState::AllocateResources { player: Color::Red, resources: vec![ResourceType::Psionics] });
         
-        match g1.systems[target_system as usize].clone() {
+        match g1.systems()[target_system as usize].clone() {
             System::Unused => panic!("System should not be unused"),
             System::Used { system_id, system_type, building_slots, ships, controlled_by, connects_to } => {
                 assert_eq!(system_id, target_system);
@@ -36,7 +36,7 @@ mod test{
             },
         }
 
-        let g2 = actions::execute_action(&g1, Action::AllocateResources { configuration: vec![(0,ResourceType::Psionics), (1,ResourceType::Material), (2,ResourceType::Psionics)] });
+        let g2 = actions::execute_action(&g1, Action::AllocateResources { configuration: vec![(0,ResourceType::Psionics), (1,ResourceType::Material), (2,ResourceType::Psionics)] }).unwrap();
 
         assert_eq!(
             g2.players.get(&Color::Red).unwrap().resource_slots,
@@ -51,7 +51,7 @@ mod test{
     #[test]
     fn test_tax_rival_city() {
         let test_setup: SetupCard = two_player_frontiers();
-        let mut game_state: GameState = board::setup_game(&test_setup);
+        let mut game_state: GameState = board::setup_game(&test_setup).unwrap();
         
         game_state.add_action_cards(&Color::Red, vec![
             ActionCard { action_type: ActionType::Administration, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) },
@@ -69,9 +69,9 @@ mod test{
             Action::MainAction {basic_action: BasicAction::Move { origin_id: 17, destination_id: 3, fresh_ships: 3, damaged_ships: 0 }},
             Action::MainAction {basic_action: BasicAction::Move { origin_id: 3, destination_id: 2, fresh_ships: 3, damaged_ships: 0 }},
             Action::MainAction {basic_action: BasicAction::Move { origin_id: 2, destination_id: 12, fresh_ships: 3, damaged_ships: 0 }}
-        ]);
+        ]).unwrap();
 
-        assert_eq!(g1.systems[target_system as usize], System::Used {
+        assert_eq!(g1.systems()[target_system as usize], System::Used {
             system_id: target_system,
             system_type: SystemType::Planet { resource: ResourceType::Material },
             building_slots: vec![BuildingSlot::Occupied { fresh: true, player: Color::Blue, building_type: BuildingType::City, used: false }],
@@ -86,9 +86,9 @@ mod test{
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Move { origin_id: 12, destination_id: 13, fresh_ships: 1, damaged_ships: 0 }},
             Action::EndTurn,
-        ]);
+        ]).unwrap();
 
-        assert_eq!(g2.systems[target_system as usize], System::Used {
+        assert_eq!(g2.systems()[target_system as usize], System::Used {
             system_id: target_system,
             system_type: SystemType::Planet { resource: ResourceType::Material },
             building_slots: vec![BuildingSlot::Occupied { fresh: true, player: Color::Blue, building_type: BuildingType::City, used: false }],
@@ -105,7 +105,7 @@ mod test{
             Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Administration, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Tax { target_system: target_system, target_player: Color::Blue }}
-            ]);
+            ]).unwrap();
 
         assert_eq!(g3.turn_state, TurnState::AllocateResources { player: Color::Red, resources: vec![ResourceType::Material] });
         assert_eq!(g3.players.get(&Color::Blue).unwrap().reserve.get(&ReserveType::Agents), Some(&9));
@@ -10,7 +10,7 @@ mod test{
 
     #[test]
     fn mobilization_then_construction() {
-        let mut game_state = board::setup_game(&two_player_frontiers());
+        let mut game_state = board::setup_game(&two_player_frontiers()).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![
             ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) },
@@ -29,7 +29,7 @@ mod test{
             Action::EndPrelude,
             Action::MainAction { basic_action: BasicAction::Move { origin_id: 17, destination_id: 16, fresh_ships: 2, damaged_ships: 0 } },
             Action::EndTurn
-        ]);
+        ]).unwrap();
 
         assert_eq!(g1.turn_state, TurnState::TrickTaking);
         assert_eq!(g1.initiative, Color::Red);
@@ -40,7 +40,7 @@ mod test{
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Build { target_system: 18, build_type: BuildType::Ship }},
             Action::EndTurn
-        ]);
+        ]).unwrap();
 
         assert_eq!(g2.initiative, Color::Red);
         assert_eq!(g2.current_player, Color::Red);
@@ -50,20 +50,20 @@ mod test{
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Build { target_system: 16, build_type: BuildType::Starport }},
             Action::EndTurn
-        ]);
+        ]).unwrap();
 
         let g4: GameState = actions::execute_actions(&g3, vec![
             Action::Surpass { card: ActionCard { action_type: ActionType::Construction, number: 6, pips: 2, declared_ambition: Some(AmbitionTypes::Empath) }, seize: None },
             Action::EndPrelude,
             Action::MainAction {basic_action: BasicAction::Build { target_system: 18, build_type: BuildType::Ship }}
-        ]);
+        ]).unwrap();
 
-        let g4 = actions::execute_action(&g4, Action::EndTurn);
+        let g4 = actions::execute_action(&g4, Action::EndTurn).unwrap();
 
         assert_eq!(g4.initiative, Color::Blue);
         assert_eq!(g4.current_player, Color::Blue);
 
-        match &g4.systems[17] {
+        match &g4.systems()[17] {
             System::Unused => panic!("Expected Used System"),
             System::Used { 
                 system_id, 
@@ -81,7 +81,7 @@ mod test{
                 },
         }
 
-        match &g4.systems[16] {
+        match &g4.systems()[16] {
             System::Unused => panic!("Expected Used System"),
             System::Used { 
                 system_id, 
@@ -99,7 +99,7 @@ mod test{
                 },
         }
 
-        match &g4.systems[18] {
+        match &g4.systems()[18] {
             System::Unused => panic!("Expected Used System"),
             System::Used { 
                 system_id, 
@@ -121,7 +121,7 @@ mod test{
 
     #[test]
     fn declare_ambition() {
-        let mut game_state = board::setup_game(&two_player_frontiers());
+        let mut game_state = board::setup_game(&two_player_frontiers()).unwrap();
 
         game_state.add_action_cards(&Color::Red, vec![
             ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) },
@@ -135,7 +135,7 @@ mod test{
 
         assert_eq!(game_state.current_player, Color::Red);
 
-        let new_game_state = actions::execute_action(&game_state, Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: Some(AmbitionTypes::Tycoon) });
+        let new_game_state = actions::execute_action(&game_state, Action::PlayLeadCard { card: ActionCard { action_type: ActionType::Mobilization, number: 2, pips: 4, declared_ambition: Some(AmbitionTypes::Tycoon) }, declare: Some(AmbitionTypes::Tycoon) }).unwrap();
 
         assert_eq!(new_game_state.ambition_markers, vec![AmbitionMarker {first_place: 2, second_place: 0, flipped: false, first_place_flipped: 4, second_place_flipped: 2},AmbitionMarker {first_place: 3, second_place: 2, flipped: false, first_place_flipped: 6, second_place_flipped: 4}]);
         assert_eq!(new_game_state.ambitions.get(&AmbitionTypes::Tycoon), Some(&Ambition{ambition_type: AmbitionTypes::Tycoon, markers: vec![AmbitionMarker {first_place: 5, second_place: 3, flipped: false, first_place_flipped: 9, second_place_flipped: 5}], discarded_resources: vec![]}))
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::{SetupOptions, two_player_frontiers};
+
+    use correspondence_arcs::data::game_state::{Action, ActionCard, ActionType, AmbitionTypes, BasicAction, Color, Dice, TurnState};
+    use correspondence_arcs::game::{Game, UndoBlocked};
+    use correspondence_arcs::replay::GameRecord;
+    use correspondence_arcs::actions;
+
+    fn new_game() -> Game {
+        Game::new(&two_player_frontiers(), 4, &SetupOptions::default()).unwrap()
+    }
+
+    fn first_card(game: &Game) -> ActionCard {
+        game.state().get_player_area(&game.state().current_player).action_cards[0].clone()
+    }
+
+    #[test]
+    fn test_nothing_to_undo(){
+        assert_eq!(new_game().can_undo(), Err(UndoBlocked::NothingToUndo));
+    }
+
+    #[test]
+    fn test_played_card_is_revealed(){
+        let game = new_game();
+        let card = first_card(&game);
+        let lead = Action::PlayLeadCard { card: card.clone(), declare: None };
+
+        let game = game.play(lead.clone()).unwrap();
+
+        assert_eq!(game.undo(), Err(UndoBlocked::CardRevealed { action: lead, card: card }));
+    }
+
+    #[test]
+    fn test_snapshots_start_before_the_last_barrier(){
+        let game = new_game();
+        let game = game.play(Action::PlayLeadCard { card: first_card(&game), declare: None }).unwrap();
+        let game = game.play(Action::EndPrelude).unwrap();
+        let game = game.play(Action::EndTurn).unwrap();
+        let game = game.play(Action::Copy { card: first_card(&game), seize: None }).unwrap();
+        let game = game.play(Action::EndPrelude).unwrap();
+        let game = game.play(Action::EndTurn).unwrap();
+        assert_eq!(game.states.len(), 7);
+
+        let next_round = game.play(Action::PlayLeadCard { card: first_card(&game), declare: None }).unwrap();
+
+        assert_eq!(next_round.states.len(), 2);
+        assert_eq!(&next_round.states[0], game.state());
+        assert_eq!(next_round.record.actions.len(), 7);
+        assert!(matches!(next_round.undo(), Err(UndoBlocked::CardRevealed { .. })));
+    }
+
+    #[test]
+    fn test_undo_restores_the_state(){
+        let game = new_game();
+        let g1 = game.play(Action::PlayLeadCard { card: first_card(&game), declare: None }).unwrap();
+        let g2 = g1.play(Action::EndPrelude).unwrap();
+
+        let undone = g2.undo().unwrap();
+
+        assert_eq!(undone, g1);
+        assert!(matches!(undone.state().turn_state, TurnState::Prelude { .. }));
+        assert_eq!(undone.record.actions.len(), 1);
+    }
+
+    #[test]
+    fn test_undo_main_action(){
+        let game = new_game();
+        let game = game.play(Action::PlayLeadCard { card: first_card(&game), declare: None }).unwrap();
+        let game = game.play(Action::EndPrelude).unwrap();
+        let main_action = actions::legal_actions(game.state()).iter().find(|a| matches!(a, Action::MainAction { .. })).unwrap();
+
+        let g1 = game.play(main_action).unwrap();
+
+        assert_eq!(g1.can_undo(), Ok(()));
+        assert_eq!(g1.undo().unwrap(), game);
+    }
+
+    #[test]
+    fn test_copied_card_stays_hidden(){
+        let game = new_game();
+        let game = game.play(Action::PlayLeadCard { card: first_card(&game), declare: None }).unwrap();
+        let game = game.play(Action::EndPrelude).unwrap();
+        let game = game.play(Action::EndTurn).unwrap();
+
+        let g1 = game.play(Action::Copy { card: first_card(&game), seize: None }).unwrap();
+
+        assert_eq!(g1.undo().unwrap(), game);
+    }
+
+    #[test]
+    fn test_dice_cannot_be_rerolled(){
+        let card = ActionCard { action_type: ActionType::Agression, number: 2, pips: 3, declared_ambition: Some(AmbitionTypes::Tycoon) };
+        let record = GameRecord::new(&two_player_frontiers(), 4, &SetupOptions::default()).unwrap();
+        let mut game_state = record.initial_state().unwrap();
+        game_state.add_action_cards(&Color::Red, vec![card.clone()]);
+        let game_state = actions::moving::move_ships(&game_state, 17, 16, 3, 0).unwrap();
+        let game_state = actions::moving::move_ships(&game_state, 16, 15, 3, 0).unwrap();
+        let game = Game { record: record, states: vec![game_state] };
+        let battle = Action::MainAction { basic_action: BasicAction::Battle { target_system: 15, target_player: Color::Blue, dice: vec![Dice::Assault] } };
+
+        let game = game.play(Action::PlayLeadCard { card: card, declare: None }).unwrap();
+        let game = game.play(Action::EndPrelude).unwrap();
+        let game = game.play(battle.clone()).unwrap();
+
+        assert_eq!(game.can_undo(), Err(UndoBlocked::RandomnessUsed { action: battle.clone() }));
+        assert_eq!(game.undo().unwrap_err().to_string(), format!("Cannot undo {:?}, dice were rolled or cards shuffled", battle));
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod test{
+    use correspondence_arcs::data::setup_cards::{SetupOptions, two_player_frontiers};
+
+    use correspondence_arcs::data::events::{CardPlay, GameEvent};
+    use correspondence_arcs::data::game_state::{Action, ActionCard, Color};
+    use correspondence_arcs::game::Game;
+    use correspondence_arcs::view::{EventView, GameView, Hand, PlayedCard, Viewer};
+
+    fn new_game() -> Game {
+        Game::new(&two_player_frontiers(), 4, &SetupOptions::default()).unwrap()
+    }
+
+    fn first_card(game: &Game) -> ActionCard {
+        game.state().get_player_area(&game.state().current_player).action_cards[0].clone()
+    }
+
+    // Red leads, then Blue copies face down
+    fn after_copy() -> (Game, ActionCard) {
+        let game = new_game();
+        let game = game.play(Action::PlayLeadCard { card: first_card(&game), declare: None }).unwrap();
+        let game = game.play(Action::EndPrelude).unwrap();
+        let game = game.play(Action::EndTurn).unwrap();
+        assert_eq!(game.state().current_player, Color::Blue);
+        let copied = first_card(&game);
+        let game = game.play(Action::Copy { card: copied.clone(), seize: None }).unwrap();
+        (game, copied)
+    }
+
+    #[test]
+    fn test_own_hand_and_opponent_count(){
+        let game = new_game();
+        let red_hand = game.state().get_player_area(&Color::Red).action_cards;
+
+        let view = game.view(&Viewer::Seat { player: Color::Red });
+
+        assert_eq!(view.players[&Color::Red].hand, Hand::Visible { cards: red_hand });
+        assert_eq!(view.players[&Color::Blue].hand, Hand::Hidden { count: 6 });
+        assert_eq!(view.action_deck, game.state().action_deck.len());
+        assert_eq!(view.court_draw_pile, game.state().court_draw_pile.len());
+        assert_eq!(view.court, game.state().court);
+    }
+
+    #[test]
+    fn test_spectator_sees_no_hands(){
+        let view = new_game().view(&Viewer::Spectator);
+
+        assert_eq!(view.players[&Color::Red].hand, Hand::Hidden { count: 6 });
+        assert_eq!(view.players[&Color::Blue].hand, Hand::Hidden { count: 6 });
+    }
+
+    #[test]
+    fn test_open_hands(){
+        let mut game_state = new_game().state().clone();
+        game_state.rules.open_hands = true;
+
+        let view = GameView::new(&game_state, &Viewer::Spectator);
+
+        assert_eq!(view.players[&Color::Blue].hand, Hand::Visible { cards: game_state.get_player_area(&Color::Blue).action_cards });
+    }
+
+    #[test]
+    fn test_face_down_copy_is_hidden(){
+        let (game, _) = after_copy();
+
+        let view = game.view(&Viewer::Seat { player: Color::Red });
+
+        assert_eq!(view.follow_cards, vec![PlayedCard { player: Color::Blue, face_up: false, card: None }]);
+        assert!(view.lead_card.unwrap().card.is_some());
+        let events = &view.events[view.events.len() - 2..];
+        assert_eq!(events, [
+            EventView::HiddenAction { player: Color::Blue },
+            EventView::HiddenCardPlayed { player: Color::Blue, play: CardPlay::Copy, seize: false }
+        ]);
+    }
+
+    #[test]
+    fn test_copy_is_visible_to_its_player(){
+        let (game, copied) = after_copy();
+
+        let view = game.view(&Viewer::Seat { player: Color::Blue });
+
+        assert_eq!(view.follow_cards, vec![PlayedCard { player: Color::Blue, face_up: false, card: Some(copied.clone()) }]);
+        assert!(view.events.contains(&EventView::Visible { event: GameEvent::CardPlayed { player: Color::Blue, card: copied, play: CardPlay::Copy, seize: false } }));
+    }
+
+    #[test]
+    fn test_copy_stays_hidden_after_the_round(){
+        let (game, copied) = after_copy();
+        let game = game.play(Action::EndPrelude).unwrap();
+        let game = game.play(Action::EndTurn).unwrap();
+        assert!(game.state().action_discard.contains(&copied));
+
+        let view = game.view(&Viewer::Seat { player: Color::Red });
+
+        assert_eq!(view.action_discard, 2);
+        assert!(view.follow_cards.is_empty());
+        assert!(!serde_json::to_string(&view).unwrap().contains(&serde_json::to_string(&copied).unwrap()));
+    }
+
+    #[test]
+    fn test_view_leaves_out_the_seed(){
+        let game = new_game();
+
+        let json = serde_json::to_string(&game.view(&Viewer::Spectator)).unwrap();
+
+        assert!(!json.contains("word_pos"));
+        assert!(!json.contains("action_cards"));
+    }
+}